@@ -12,10 +12,53 @@ fn parse(file_name: &str) {
         .expect("during test");
 }
 
+fn many_references_document() -> String {
+    let large_object = (0..200)
+        .map(|i| format!("key{} = value{}", i, i))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let references = (0..200)
+        .map(|i| format!("ref{} = ${{shared}}", i))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("{}\nshared {{\n{}\n}}", references, large_object)
+}
+
+fn repeated_strings_document() -> String {
+    (0..2000)
+        .map(|i| {
+            format!(
+                "item{} {{ level = {}, tag = shared }}",
+                i,
+                ["debug", "info", "warn"][i % 3]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("parse test01.conf", |b| {
         b.iter(|| parse("benches/data/test01.conf"))
     });
+    let many_references = hocon::HoconLoader::new()
+        .no_system()
+        .load_str(&many_references_document())
+        .expect("during test");
+    c.bench_function("resolve many references to one object", |b| {
+        b.iter(|| many_references.clone().hocon().expect("during test"))
+    });
+    let repeated_strings = hocon::HoconLoader::new()
+        .no_system()
+        .load_str(&repeated_strings_document())
+        .expect("during test");
+    c.bench_function("resolve repeated strings", |b| {
+        b.iter(|| repeated_strings.clone().hocon().expect("during test"))
+    });
+    let repeated_strings = repeated_strings.intern_strings();
+    c.bench_function("resolve repeated strings with interning", |b| {
+        b.iter(|| repeated_strings.clone().hocon().expect("during test"))
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -16,18 +16,38 @@ pub enum Error {
         /// Path to the file being read
         path: String,
     },
+    /// Error decoding a document, that is not valid UTF-8
+    #[error("Invalid UTF-8 at byte {offset}")]
+    Encoding {
+        /// Offset of the first invalid byte
+        offset: usize,
+    },
     /// Error while parsing a document. The document is not valid HOCON
-    #[error("Error wile parsing document")]
-    Parse,
+    #[error("Parse error at line {line}, column {column}")]
+    Parse {
+        /// Line of the document where the error was found, starting at 1
+        line: usize,
+        /// Column in the line where the error was found, starting at 1
+        column: usize,
+        /// Start of the input that could not be parsed
+        snippet: String,
+    },
     /// Error including a document
-    #[error("Error including document at '{path:?}'")]
+    #[error("Error including document at '{path:?}'{}", from_chain(.chain))]
     Include {
         /// Path of the included file
         path: String,
+        /// Files through which the document was included, starting with the file containing
+        /// the include and ending with the loaded file
+        chain: Vec<String>,
     },
     /// Error processing deep includes. You can change the maximum depth using max_include_depth
     #[error("Error processing deep includes")]
     TooManyIncludes,
+    /// Error because the document has more keys and array elements than allowed. You can
+    /// change the maximum using max_keys
+    #[error("Error processing a document with too many keys")]
+    TooManyKeys,
     /// Error processing includes from a str source. This is not allowed
     #[error("Error processing includes from a str source")]
     IncludeNotAllowedFromStr,
@@ -40,6 +60,13 @@ pub enum Error {
         /// Key that was searched
         key: String,
     },
+    /// Error resolving a substitution that is part of a cycle, or that goes through too many
+    /// nested substitutions. You can change the maximum depth using max_substitution_depth
+    #[error("Error resolving substitution cycle at '{key:?}'")]
+    SubstitutionCycle {
+        /// Key of the substitution that could not be resolved
+        key: String,
+    },
     /// Error getting a value because key is not present
     #[error("Error getting a value because key is not present")]
     MissingKey,
@@ -52,6 +79,21 @@ pub enum Error {
         /// Error message returned from deserialization
         message: String,
     },
+    /// Error converting a value because it is not of the expected type
+    #[error("Error converting a value, expected {expected}")]
+    TypeMismatch {
+        /// Type that was expected
+        expected: String,
+    },
+}
+
+// Describe the files through which a document was included
+fn from_chain(chain: &[String]) -> String {
+    if chain.is_empty() {
+        String::new()
+    } else {
+        format!(" (from {})", chain.join(" from "))
+    }
 }
 
 /// this is only needed because this crate heavily relies on Clone and io:Error doesnt implement Clone
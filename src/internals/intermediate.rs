@@ -1,4 +1,5 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use crate::{Hocon, HoconLoaderConfig};
@@ -222,16 +223,117 @@ impl Child {
     }
 }
 
+// Finalized values of substitution targets, keyed by the path of the target and the path of
+// the include it was resolved from
+type SubstitutionCache = HashMap<(Vec<HoconValue>, Vec<HoconValue>), Hocon>;
+
 #[derive(Clone, Debug)]
 pub(crate) struct HoconIntermediate {
     pub(crate) tree: Node,
+    pub(crate) substitutions: RefCell<SubstitutionCache>,
+    // number of substitutions currently being resolved one inside the other
+    pub(crate) substitution_depth: Cell<u8>,
+    // set when a value depends on the substitution being resolved, like when a self-reference
+    // is replaced or the maximum depth is reached, so that it is not cached
+    pub(crate) depends_on_context: Cell<bool>,
 }
 
 impl HoconIntermediate {
+    pub(crate) fn new(tree: Node) -> Self {
+        Self {
+            tree,
+            substitutions: RefCell::new(HashMap::new()),
+            substitution_depth: Cell::new(0),
+            depends_on_context: Cell::new(false),
+        }
+    }
+
+    fn substitution_cache_key(
+        target: &[HoconValue],
+        included_path: &Option<Vec<HoconValue>>,
+    ) -> Option<(Vec<HoconValue>, Vec<HoconValue>)> {
+        let included_path = included_path
+            .iter()
+            .flatten()
+            .flat_map(HoconValue::to_path)
+            .collect::<Vec<_>>();
+        if target.iter().chain(included_path.iter()).all(|item| {
+            matches!(
+                item,
+                HoconValue::String(_) | HoconValue::Integer(_) | HoconValue::Null(_)
+            )
+        }) {
+            Some((target.to_vec(), included_path))
+        } else {
+            None
+        }
+    }
+
+    // Find and finalize the target of a substitution, reusing the result if the same target
+    // was already resolved during this pass
+    pub(crate) fn finalize_substitution(
+        &self,
+        config: &HoconLoaderConfig,
+        target: Vec<HoconValue>,
+        included_path: Option<Vec<HoconValue>>,
+    ) -> Result<Hocon, crate::Error> {
+        let cache_key = Self::substitution_cache_key(&target, &included_path);
+        if let Some(cached) = cache_key
+            .as_ref()
+            .and_then(|key| self.substitutions.borrow().get(key).cloned())
+        {
+            return Ok(cached);
+        }
+        let depth = self.substitution_depth.get();
+        if depth >= config.max_substitution_depth {
+            self.depends_on_context.set(true);
+            return Ok(public_bad_value_or_err!(
+                config,
+                crate::Error::SubstitutionCycle {
+                    key: target
+                        .into_iter()
+                        .map(HoconValue::string_value)
+                        .collect::<Vec<_>>()
+                        .join(".")
+                }
+            ));
+        }
+        self.substitution_depth.set(depth + 1);
+        let outer_depends_on_context = self.depends_on_context.replace(false);
+        let finalized = self
+            .tree
+            .find_key(config, target.clone())
+            .and_then(|v| v.finalize(self, config, included_path, Some(target)));
+        self.substitution_depth.set(depth);
+        let depends_on_context = self.depends_on_context.get();
+        self.depends_on_context
+            .set(outer_depends_on_context || depends_on_context);
+        let finalized = finalized?;
+        if let (Some(key), false) = (cache_key, depends_on_context) {
+            self.substitutions
+                .borrow_mut()
+                .insert(key, finalized.clone());
+        }
+        Ok(finalized)
+    }
+
     pub(crate) fn finalize(self, config: &HoconLoaderConfig) -> Result<Hocon, crate::Error> {
         #[allow(clippy::redundant_clone)]
         // looks like https://github.com/rust-lang/rust-clippy/issues/5707
         let refself = &self.clone();
-        self.tree.finalize(refself, config, None, None)
+        let hocon = self.tree.finalize(refself, config, None, None)?;
+        match config.max_keys {
+            Some(max_keys) if Self::count_keys(&hocon) > max_keys => Err(crate::Error::TooManyKeys),
+            _ => Ok(hocon),
+        }
+    }
+
+    // Count keys of objects and elements of arrays, including nested ones
+    fn count_keys(hocon: &Hocon) -> usize {
+        match hocon {
+            Hocon::Hash(hash) => hash.len() + hash.values().map(Self::count_keys).sum::<usize>(),
+            Hocon::Array(array) => array.len() + array.iter().map(Self::count_keys).sum::<usize>(),
+            _ => 0,
+        }
     }
 }
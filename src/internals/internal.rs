@@ -1,23 +1,44 @@
 use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use std::rc::Rc;
 
-use crate::HoconLoaderConfig;
+use crate::{HoconLoaderConfig, MergeStrategy};
 
 use super::intermediate::{Child, HoconIntermediate, Node};
 use super::value::HoconValue;
 
+// An included file, url or classpath resource, with an optional alias under which to nest
+// the included document. A file can also have the encoding to use to read it, UTF-8 being
+// used otherwise
 pub(crate) enum Include<'a> {
-    File(Cow<'a, str>),
-    Url(Cow<'a, str>),
+    File(Cow<'a, str>, Option<Cow<'a, str>>, Option<HoconValue>),
+    Url(Cow<'a, str>, Option<HoconValue>),
+    Classpath(Cow<'a, str>, Option<HoconValue>),
 }
 impl<'a> Include<'a> {
     fn included(&self) -> &Cow<'a, str> {
         match self {
-            Include::File(s) => s,
-            Include::Url(s) => s,
+            Include::File(s, _, _) => s,
+            Include::Url(s, _) => s,
+            Include::Classpath(s, _) => s,
+        }
+    }
+
+    fn alias(&self) -> Option<&HoconValue> {
+        match self {
+            Include::File(_, _, alias) => alias.as_ref(),
+            Include::Url(_, alias) => alias.as_ref(),
+            Include::Classpath(_, alias) => alias.as_ref(),
+        }
+    }
+
+    pub(crate) fn with_alias(self, alias: Option<HoconValue>) -> Self {
+        match self {
+            Include::File(s, encoding, _) => Include::File(s, encoding, alias),
+            Include::Url(s, _) => Include::Url(s, alias),
+            Include::Classpath(s, _) => Include::Classpath(s, alias),
         }
     }
 }
@@ -45,15 +66,21 @@ impl HoconInternal {
                 .map(|(path, value)| {
                     (
                         path.split('.')
-                            .map(|s| HoconValue::String(String::from(s)))
+                            .map(|s| HoconValue::String(s.into()))
                             .collect(),
-                        HoconValue::String(value),
+                        HoconValue::String(value.into()),
                     )
                 })
                 .collect(),
         }
     }
 
+    pub(crate) fn from_source(source: String) -> Self {
+        Self {
+            internal: vec![(vec![], HoconValue::Source(source))],
+        }
+    }
+
     pub(crate) fn from_value(v: HoconValue) -> Self {
         Self {
             internal: vec![(vec![], v)],
@@ -75,11 +102,44 @@ impl HoconInternal {
         }
     }
 
+    #[cfg(feature = "serde-support")]
+    pub(crate) fn from_json(value: &serde_json::Value, config: &HoconLoaderConfig) -> Self {
+        use serde_json::Value;
+
+        match value {
+            Value::Null => Self::from_value(HoconValue::UnquotedString("null".into())),
+            Value::Bool(b) => Self::from_value(HoconValue::Boolean(*b)),
+            Value::Number(n) => Self::from_value(match n.as_i64() {
+                Some(i) => HoconValue::Integer(i),
+                None => HoconValue::Real(n.as_f64().unwrap_or(f64::NAN), None),
+            }),
+            Value::String(s) => Self::from_value(HoconValue::String(s.as_str().into())),
+            Value::Array(values) => Self::from_array(
+                values
+                    .iter()
+                    .map(|value| Self::from_json(value, config))
+                    .collect(),
+                config,
+            ),
+            Value::Object(object) => Self::from_object(
+                object
+                    .iter()
+                    .flat_map(|(key, value)| {
+                        Self::from_object(Self::from_json(value, config).internal)
+                            .add_to_path(vec![HoconValue::String(key.as_str().into())])
+                            .internal
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
     fn add_root_to_includes(k: Vec<HoconValue>, v: HoconValue) -> (Vec<HoconValue>, HoconValue) {
         match v {
             HoconValue::Included {
                 value,
                 original_path,
+                source,
                 ..
             } => {
                 let root = k
@@ -93,6 +153,7 @@ impl HoconInternal {
                         value,
                         include_root: Some(root),
                         original_path,
+                        source,
                     },
                 )
             }
@@ -113,32 +174,62 @@ impl HoconInternal {
         }
     }
 
-    pub(crate) fn from_array(a: Vec<HoconInternal>) -> Self {
+    // Give new ids to the items of a document that was already parsed, so that they stay
+    // unique when loading it again
+    pub(crate) fn with_new_item_ids(mut self, config: &HoconLoaderConfig) -> Self {
+        let mut new_ids: HashMap<String, String> = HashMap::new();
+        let mut new_id = |id: &str| {
+            new_ids
+                .entry(String::from(id))
+                .or_insert_with(|| config.next_item_id())
+                .clone()
+        };
+        for (path, value) in self.internal.iter_mut() {
+            path.iter_mut()
+                .chain(std::iter::once(value))
+                .for_each(|value| value.renew_item_ids(&mut new_id));
+        }
+        self
+    }
+
+    pub(crate) fn from_array(a: Vec<HoconInternal>, config: &HoconLoaderConfig) -> Self {
         let mut indexer: Box<dyn Fn(i64) -> HoconValue> = Box::new(HoconValue::Integer);
         if !a.is_empty() && a[0].internal.len() == 1 {
             if let HoconValue::PathSubstitutionInParent(_) = a[0].internal[0].1 {
-                let index_prefix = uuid::Uuid::new_v4().hyphenated().to_string();
+                let index_prefix = config.next_item_id();
                 indexer = Box::new(move |i| HoconValue::Null(format!("{}-{}", index_prefix, i)));
             }
         }
+        // mark where this array starts, to add it after an existing array at the same path
+        let array_start = if config.merge_strategy == MergeStrategy::ConcatArrays {
+            vec![(vec![], HoconValue::ArrayStart)]
+        } else {
+            vec![]
+        };
         if a.is_empty() {
             Self {
-                internal: vec![(vec![], HoconValue::EmptyArray)],
+                internal: array_start
+                    .into_iter()
+                    .chain(std::iter::once((vec![], HoconValue::EmptyArray)))
+                    .collect(),
             }
         } else {
             Self {
-                internal: a
+                internal: array_start
                     .into_iter()
-                    .enumerate()
-                    .flat_map(|(i, hw)| {
-                        Self {
-                            internal: hw.internal,
-                        }
-                        .add_to_path(vec![indexer(i as i64)])
-                        .internal
-                        .into_iter()
-                    })
-                    .map(|(k, v)| Self::add_root_to_includes(k, v))
+                    .chain(
+                        a.into_iter()
+                            .enumerate()
+                            .flat_map(|(i, hw)| {
+                                Self {
+                                    internal: hw.internal,
+                                }
+                                .add_to_path(vec![indexer(i as i64)])
+                                .internal
+                                .into_iter()
+                            })
+                            .map(|(k, v)| Self::add_root_to_includes(k, v)),
+                    )
                     .collect(),
             }
         }
@@ -151,66 +242,107 @@ impl HoconInternal {
         if config.include_depth > config.max_include_depth {
             Ok(Self {
                 internal: vec![(
-                    vec![HoconValue::String(included.included().to_string())],
-                    bad_value_or_err!(config, crate::Error::TooManyIncludes),
+                    vec![HoconValue::String(included.included().as_ref().into())],
+                    include_bad_value_or_err!(config, crate::Error::TooManyIncludes),
                 )],
             })
         } else if config.file_meta.is_none() {
             Ok(Self {
                 internal: vec![(
-                    vec![HoconValue::String(included.included().to_string())],
-                    bad_value_or_err!(config, crate::Error::IncludeNotAllowedFromStr),
+                    vec![HoconValue::String(included.included().as_ref().into())],
+                    include_bad_value_or_err!(config, crate::Error::IncludeNotAllowedFromStr),
                 )],
             })
         } else {
+            let source = included.included().to_string();
+            let alias = included.alias().cloned();
             let included_parsed = match included {
-                Include::File(ref path) => {
+                Include::File(ref path, _, _) | Include::Classpath(ref path, _)
+                    if !config.file_include =>
+                {
+                    Err(config.include_error(path))
+                }
+                Include::File(ref path, ref encoding, _) => {
                     let include_config = config
-                        .included_from()
+                        .included_from(path)
                         .with_file(std::path::Path::new(path.as_ref()).to_path_buf());
-                    include_config
-                        .read_file()
-                        .map_err(|_| crate::error::Error::Include {
-                            path: path.to_string(),
-                        })
-                        .and_then(|s| include_config.parse_str_to_internal(s))
+                    include_config.parse_file_cached(encoding.as_deref(), |include_config| {
+                        include_config
+                            .read_file_with_encoding(encoding.as_deref())
+                            .map_err(|_| config.include_error(path))
+                            .and_then(|s| include_config.parse_str_to_internal(s))
+                    })
                 }
-                #[cfg(feature = "url-support")]
-                Include::Url(ref url) => {
+                Include::Classpath(ref name, _) => {
+                    let not_found = || config.include_error(name);
                     config
-                        .load_url(url)
-                        .map_err(|_| crate::error::Error::Include {
-                            path: url.to_string(),
+                        .classpath_resolver
+                        .as_ref()
+                        .and_then(|resolver| (resolver.0)(name))
+                        .ok_or_else(not_found)
+                        .and_then(|path| {
+                            // the resolved path is not relative to the current file
+                            let include_config = HoconLoaderConfig {
+                                file_meta: Some(crate::ConfFileMeta::from_path(path)),
+                                ..config.included_from(name)
+                            };
+                            include_config.parse_file_cached(None, |include_config| {
+                                include_config
+                                    .read_file()
+                                    .map_err(|_| not_found())
+                                    .and_then(|s| include_config.parse_str_to_internal(s))
+                            })
                         })
                 }
+                #[cfg(feature = "url-support")]
+                Include::Url(ref url, _) => {
+                    config.load_url(url).map_err(|_| config.include_error(url))
+                }
                 #[cfg(not(feature = "url-support"))]
                 _ => Err(crate::error::Error::DisabledExternalUrl),
             };
 
-            match included_parsed {
-                Ok(included) => Ok(Self {
+            let included = match included_parsed {
+                Ok(included) => Self {
                     internal: included
                         .internal
                         .into_iter()
                         .map(|(path, value)| {
-                            (
-                                path.clone(),
-                                HoconValue::Included {
-                                    value: Box::new(value),
-                                    original_path: path,
-                                    include_root: None,
+                            let included = |value| HoconValue::Included {
+                                value: Box::new(value),
+                                original_path: path.clone(),
+                                include_root: None,
+                                source: source.clone(),
+                            };
+                            let value = match value {
+                                // keep concatenation visible when merging, so that it can
+                                // append to an array defined outside of the included file
+                                HoconValue::ToConcatToArray {
+                                    value,
+                                    original_path,
+                                    item_id,
+                                } => HoconValue::ToConcatToArray {
+                                    value: Box::new(included(*value)),
+                                    original_path,
+                                    item_id,
                                 },
-                            )
+                                value => included(value),
+                            };
+                            (path.clone(), value)
                         })
                         .collect(),
-                }),
-                Err(error) => Ok(Self {
+                },
+                Err(error) => Self {
                     internal: vec![(
-                        vec![HoconValue::String(included.included().to_string())],
-                        bad_value_or_err!(config, error),
+                        vec![HoconValue::String(included.included().as_ref().into())],
+                        include_bad_value_or_err!(config, error),
                     )],
-                }),
-            }
+                },
+            };
+            Ok(match alias {
+                Some(alias) => included.add_to_path(vec![alias]),
+                None => included,
+            })
         }
     }
 
@@ -250,6 +382,18 @@ impl HoconInternal {
     pub(crate) fn merge(
         self,
         config: &HoconLoaderConfig,
+    ) -> Result<HoconIntermediate, crate::Error> {
+        self.merge_with_provenance(config, None, None)
+    }
+
+    // Merge the values, and if `provenance` is set, record for each leaf path the source of
+    // the value that was kept. If `number_texts` is set, record for each leaf path the text
+    // of the real number that was kept, when it was preserved
+    pub(crate) fn merge_with_provenance(
+        self,
+        config: &HoconLoaderConfig,
+        mut provenance: Option<&mut HashMap<String, String>>,
+        mut number_texts: Option<&mut HashMap<String, String>>,
     ) -> Result<HoconIntermediate, crate::Error> {
         let root = Rc::new(Child {
             key: HoconValue::Temp,
@@ -262,23 +406,66 @@ impl HoconInternal {
         let mut concatenated_arrays: HashMap<Path, HashMap<HoconValue, i64>> = HashMap::new();
 
         let mut last_path_encoutered = vec![];
+        let mut array_offsets: HashMap<Path, i64> = HashMap::new();
+        let mut current_source = String::new();
+        // number of keys and array elements currently in the tree, to stop as soon as there
+        // are too many
+        let mut nb_keys = 0;
+        // strings already in the tree, to share them when interning strings
+        let mut strings = HashSet::new();
         for (raw_path, item) in self.internal {
             if raw_path.is_empty() {
+                if let HoconValue::Source(source) = item {
+                    current_source = source;
+                }
                 continue;
             }
+            // the start of an object replaces the previous value when objects are not merged
+            let mut item = match item
+                .replace_object_start(config.merge_strategy == MergeStrategy::ReplaceAll)
+            {
+                Some(item) => item,
+                None => continue,
+            };
 
-            let full_path = raw_path
+            let item_source = item
+                .included_source()
+                .map(String::from)
+                .unwrap_or_else(|| current_source.clone());
+            let item_number_text = item.number_text().map(String::from);
+
+            let mut full_path = raw_path
                 .clone()
                 .into_iter()
                 .flat_map(|path_item| match path_item {
                     HoconValue::UnquotedString(s) => s
                         .trim()
                         .split('.')
-                        .map(|s| HoconValue::String(String::from(s)))
+                        .map(|s| HoconValue::String(s.into()))
                         .collect(),
                     _ => vec![path_item],
                 })
                 .collect::<Vec<_>>();
+            if config.intern_strings {
+                full_path
+                    .iter_mut()
+                    .chain(std::iter::once(&mut item))
+                    .for_each(|value| value.intern_strings(&mut strings));
+            }
+
+            if let HoconValue::ArrayStart = item {
+                let array_path = Self::shift_array_indexes(full_path, &array_offsets);
+                let existing_len = Self::array_len_at(&root, &array_path);
+                array_offsets.insert(array_path, existing_len);
+                continue;
+            }
+            if let HoconValue::EmptyArray = item {
+                // an empty array doesn't replace an existing one when concatenating arrays
+                let array_path = Self::shift_array_indexes(full_path.clone(), &array_offsets);
+                if array_offsets.get(&array_path).cloned().unwrap_or(0) > 0 {
+                    continue;
+                }
+            }
 
             let (leaf_value, path) = match item {
                 HoconValue::PathSubstitutionInParent(v) => {
@@ -286,6 +473,7 @@ impl HoconInternal {
                         target: v,
                         optional: false,
                         original: None,
+                        default: None,
                     }
                     .substitute(config, &root, &full_path);
                     (subst, full_path.into_iter().rev().skip(1).rev().collect())
@@ -303,30 +491,39 @@ impl HoconInternal {
                         .rev()
                         .cloned()
                         .collect();
-                    let existing_array = concatenated_arrays
-                        .entry(concat_root.clone())
-                        .or_insert_with(HashMap::new);
-                    let nb_elems = existing_array.keys().len();
-                    let idx = existing_array
-                        .entry(HoconValue::String(item_id.clone()))
-                        .or_insert(nb_elems as i64);
-                    (
-                        value.substitute(config, &root, &full_path),
-                        concat_root
-                            .into_iter()
-                            .chain(std::iter::once(HoconValue::Integer(*idx)))
-                            .chain(original_path.into_iter().flat_map(|path_item| {
-                                match path_item {
-                                    HoconValue::UnquotedString(s) => s
-                                        .trim()
-                                        .split('.')
-                                        .map(|s| HoconValue::String(String::from(s)))
-                                        .collect(),
-                                    _ => vec![path_item],
-                                }
-                            }))
-                            .collect(),
-                    )
+                    if Self::is_scalar_at(&root, &concat_root) {
+                        // `a += v` is `a = ${?a} [v]`, which can't concatenate a scalar to an array
+                        let err = crate::Error::TypeMismatch {
+                            expected: String::from("an array to concatenate to"),
+                        };
+                        let bad_value = bad_value_or_err!(config, err);
+                        (Ok(Node::Leaf(bad_value)), concat_root)
+                    } else {
+                        let existing_array = concatenated_arrays
+                            .entry(concat_root.clone())
+                            .or_insert_with(HashMap::new);
+                        let nb_elems = existing_array.keys().len();
+                        let idx = existing_array
+                            .entry(HoconValue::String(item_id.as_str().into()))
+                            .or_insert(nb_elems as i64);
+                        (
+                            value.substitute(config, &root, &full_path),
+                            concat_root
+                                .into_iter()
+                                .chain(std::iter::once(HoconValue::Integer(*idx)))
+                                .chain(original_path.into_iter().flat_map(|path_item| {
+                                    match path_item {
+                                        HoconValue::UnquotedString(s) => s
+                                            .trim()
+                                            .split('.')
+                                            .map(|s| HoconValue::String(s.into()))
+                                            .collect(),
+                                        _ => vec![path_item],
+                                    }
+                                }))
+                                .collect(),
+                        )
+                    }
                 }
                 HoconValue::PathSubstitution { ref target, .. } => {
                     let value = concatenated_arrays
@@ -339,6 +536,32 @@ impl HoconInternal {
                     (item.substitute(config, &root, &full_path), full_path)
                 }
                 v => {
+                    if let HoconValue::Included {
+                        value,
+                        original_path,
+                        ..
+                    } = &v
+                    {
+                        if let HoconValue::PathSubstitution { target, .. } = value.as_ref() {
+                            // an included substitution is first resolved from the root of the
+                            // include, keep track of the elements of the array it targets so
+                            // that `+=` appends after them
+                            let mut included_target = full_path
+                                .iter()
+                                .take(full_path.len() - original_path.len())
+                                .flat_map(HoconValue::to_path)
+                                .collect::<Vec<_>>();
+                            included_target.append(&mut target.to_path());
+                            let value = concatenated_arrays
+                                .get(&included_target)
+                                .or_else(|| concatenated_arrays.get(&target.to_path()))
+                                .cloned()
+                                .unwrap_or_default();
+                            concatenated_arrays
+                                .entry(full_path.clone())
+                                .or_insert(value);
+                        }
+                    }
                     let mut checked_path: Path = vec![];
                     for item in full_path.clone() {
                         if let HoconValue::Integer(idx) = item {
@@ -354,6 +577,8 @@ impl HoconInternal {
                 }
             };
 
+            let path = Self::shift_array_indexes(path, &array_offsets);
+
             let mut current_path = vec![];
             let mut current_node = Rc::clone(&root);
             let mut old_node_value_for_optional_substitution = None;
@@ -367,6 +592,7 @@ impl HoconInternal {
                         });
 
                         old_node_value_for_optional_substitution = Some(old_value.clone());
+                        nb_keys += 1;
 
                         (Rc::clone(&new_child), vec![Rc::clone(&new_child)])
                     }
@@ -386,6 +612,7 @@ impl HoconInternal {
                                     value: RefCell::new(Node::Leaf(HoconValue::Temp)),
                                 });
                                 new_children.push(Rc::clone(&new_child));
+                                nb_keys = nb_keys - Self::count_keys_of_children(children) + 1;
 
                                 (new_child, new_children)
                             }
@@ -431,7 +658,11 @@ impl HoconInternal {
                                     }
                                 };
 
+                                if new_children.is_empty() {
+                                    nb_keys -= Self::count_keys_of_children(children);
+                                }
                                 new_children.push(Rc::clone(&new_child));
+                                nb_keys += 1;
                                 (new_child, new_children)
                             }
                         }
@@ -446,28 +677,139 @@ impl HoconInternal {
             }
             let mut leaf = current_node.value.borrow_mut();
 
-            *leaf = match leaf_value? {
+            let new_leaf = match leaf_value? {
                 Node::Leaf(HoconValue::PathSubstitution {
                     target,
                     optional,
                     original: previously_set_original,
+                    default,
                 }) => Node::Leaf(HoconValue::PathSubstitution {
                     target,
                     optional,
                     original: previously_set_original
                         .or_else(|| old_node_value_for_optional_substitution.map(Box::new)),
+                    default,
                 }),
                 v => v,
             };
+            nb_keys += Self::count_keys(&new_leaf);
+            nb_keys -= Self::count_keys(&std::mem::replace(&mut *leaf, new_leaf));
+            if matches!(config.max_keys, Some(max_keys) if nb_keys > max_keys) {
+                return Err(crate::Error::TooManyKeys);
+            }
+            if provenance.is_some() || number_texts.is_some() {
+                let key = current_path
+                    .iter()
+                    .map(|path_item| match path_item {
+                        HoconValue::String(s) | HoconValue::UnquotedString(s) => s.to_string(),
+                        HoconValue::Integer(i) => i.to_string(),
+                        other => other.clone().string_value(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(".");
+                // a value replacing an object or replaced by an object hides previous values
+                let is_hidden = |existing: &String| {
+                    existing.starts_with(&format!("{}.", key))
+                        || key.starts_with(&format!("{}.", existing))
+                };
+                if let Some(provenance) = provenance.as_mut() {
+                    provenance.retain(|existing, _| !is_hidden(existing));
+                    provenance.insert(key.clone(), item_source);
+                }
+                if let Some(number_texts) = number_texts.as_mut() {
+                    number_texts.retain(|existing, _| !is_hidden(existing));
+                    match item_number_text {
+                        Some(text) => number_texts.insert(key, text),
+                        None => number_texts.remove(&key),
+                    };
+                }
+            }
             last_path_encoutered = current_path;
         }
 
-        Ok(HoconIntermediate {
-            tree: Rc::try_unwrap(root)
+        Ok(HoconIntermediate::new(
+            Rc::try_unwrap(root)
                 .expect("error getting Rc")
                 .value
                 .into_inner(),
-        })
+        ))
+    }
+}
+
+impl HoconInternal {
+    // Shift the indexes of arrays in `path` so that an array defined again is added after
+    // the elements already present instead of replacing them
+    fn shift_array_indexes(path: Path, array_offsets: &HashMap<Path, i64>) -> Path {
+        if array_offsets.is_empty() {
+            return path;
+        }
+        let mut shifted_path: Path = vec![];
+        for path_item in path {
+            let shifted_item = match path_item {
+                HoconValue::Integer(idx) => HoconValue::Integer(
+                    idx + array_offsets.get(&shifted_path).cloned().unwrap_or(0),
+                ),
+                path_item => path_item,
+            };
+            shifted_path.push(shifted_item);
+        }
+        shifted_path
+    }
+
+    fn is_scalar_at(node: &Rc<Child>, path: &[HoconValue]) -> bool {
+        fn is_scalar(value: &HoconValue) -> bool {
+            match value {
+                HoconValue::Real(..)
+                | HoconValue::Integer(_)
+                | HoconValue::String(_)
+                | HoconValue::UnquotedString(_)
+                | HoconValue::Boolean(_)
+                | HoconValue::Concat(_)
+                | HoconValue::BadValue(_) => true,
+                HoconValue::Included { value, .. } => is_scalar(value),
+                _ => false,
+            }
+        }
+        match (node.value.borrow().deref(), path.split_first()) {
+            (Node::Leaf(value), None) => is_scalar(value),
+            (Node::Node { children, .. }, Some((first, remaining))) => children
+                .iter()
+                .find(|child| &child.key == first)
+                .map(|child| Self::is_scalar_at(child, remaining))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    // Count keys of objects and elements of arrays in `node`, including nested ones
+    fn count_keys(node: &Node) -> usize {
+        match node {
+            Node::Leaf(_) => 0,
+            Node::Node { children, .. } => Self::count_keys_of_children(children),
+        }
+    }
+
+    fn count_keys_of_children(children: &[Rc<Child>]) -> usize {
+        children.len()
+            + children
+                .iter()
+                .map(|child| Self::count_keys(&child.value.borrow()))
+                .sum::<usize>()
+    }
+
+    fn array_len_at(node: &Rc<Child>, path: &[HoconValue]) -> i64 {
+        match (node.value.borrow().deref(), path.split_first()) {
+            (Node::Node { children, .. }, None) => match children.first() {
+                Some(child) if matches!(child.key, HoconValue::Integer(_)) => children.len() as i64,
+                _ => 0,
+            },
+            (Node::Node { children, .. }, Some((first, remaining))) => children
+                .iter()
+                .find(|child| &child.key == first)
+                .map(|child| Self::array_len_at(child, remaining))
+                .unwrap_or(0),
+            (Node::Leaf(_), _) => 0,
+        }
     }
 }
 
@@ -477,11 +819,12 @@ pub(crate) type Hash = Vec<(Path, HoconValue)>;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
 
     #[test]
     fn max_depth_of_include() {
         let val = dbg!(HoconInternal::from_include(
-            Include::File(Cow::from("file.conf")),
+            Include::File(Cow::from("file.conf"), None, None),
             &HoconLoaderConfig {
                 include_depth: 15,
                 file_meta: Some(crate::ConfFileMeta::from_path(
@@ -496,17 +839,79 @@ mod tests {
             val,
             HoconInternal {
                 internal: vec![(
-                    vec![HoconValue::String(String::from("file.conf"))],
+                    vec![HoconValue::String("file.conf".into())],
                     HoconValue::BadValue(crate::Error::TooManyIncludes)
                 )]
             }
         );
     }
 
+    #[test]
+    fn same_document_parses_to_same_internal() {
+        let parse = || {
+            HoconLoaderConfig::default()
+                .parse_str_to_internal(crate::FileRead::from_file_type(
+                    &crate::FileType::Hocon,
+                    String::from("a += { b : 1, c : 2 }\na += 3\nd = [${a}, 4]"),
+                ))
+                .expect("during test")
+        };
+
+        assert_eq!(format!("{:?}", parse()), format!("{:?}", parse()));
+    }
+
+    #[test]
+    fn interned_strings_are_shared_in_merged_tree() {
+        fn collect_strings(node: &Node, strings: &mut Vec<Arc<str>>) {
+            match node {
+                Node::Leaf(HoconValue::String(s)) | Node::Leaf(HoconValue::UnquotedString(s)) => {
+                    strings.push(Arc::clone(s))
+                }
+                Node::Node { children, .. } => children.iter().for_each(|child| {
+                    if let HoconValue::String(key) = &child.key {
+                        strings.push(Arc::clone(key));
+                    }
+                    collect_strings(&child.value.borrow(), strings);
+                }),
+                _ => (),
+            }
+        }
+        let strings_named = |config: &HoconLoaderConfig, name: &str| {
+            let merged = config
+                .parse_str_to_internal(crate::FileRead::from_file_type(
+                    &crate::FileType::Hocon,
+                    String::from(r#"{ a = "blue", b { a = "blue" }, c = [{ a = "blue" }] }"#),
+                ))
+                .expect("during test")
+                .merge(config)
+                .expect("during test");
+            let mut strings = vec![];
+            collect_strings(&merged.tree, &mut strings);
+            strings
+                .into_iter()
+                .filter(|s| &**s == name)
+                .collect::<Vec<_>>()
+        };
+
+        let config = HoconLoaderConfig {
+            intern_strings: true,
+            ..Default::default()
+        };
+        for name in &["a", "blue"] {
+            let strings = strings_named(&config, name);
+            assert_eq!(strings.len(), 3);
+            assert!(strings.iter().all(|s| Arc::ptr_eq(s, &strings[0])));
+        }
+
+        let strings = strings_named(&HoconLoaderConfig::default(), "blue");
+        assert_eq!(strings.len(), 3);
+        assert!(!Arc::ptr_eq(&strings[0], &strings[1]));
+    }
+
     #[test]
     fn missing_file_included() {
         let val = dbg!(HoconInternal::from_include(
-            Include::File(Cow::from("file.conf")),
+            Include::File(Cow::from("file.conf"), None, None),
             &HoconLoaderConfig {
                 include_depth: 5,
                 file_meta: Some(crate::ConfFileMeta::from_path(
@@ -521,9 +926,10 @@ mod tests {
             val,
             HoconInternal {
                 internal: vec![(
-                    vec![HoconValue::String(String::from("file.conf"))],
+                    vec![HoconValue::String("file.conf".into())],
                     HoconValue::BadValue(crate::Error::Include {
-                        path: String::from("file.conf")
+                        path: String::from("file.conf"),
+                        chain: vec![],
                     })
                 )]
             }
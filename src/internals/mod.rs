@@ -10,6 +10,17 @@ pub(crate) mod macros {
         };
     }
 
+    // An error including a document is also returned when only includes are strict
+    macro_rules! include_bad_value_or_err {
+        ( $config:expr, $err:expr ) => {
+            if $config.strict || $config.strict_include {
+                return Err($err);
+            } else {
+                HoconValue::BadValue($err)
+            }
+        };
+    }
+
     macro_rules! public_bad_value_or_err {
         ( $config:expr, $err:expr ) => {
             if $config.strict {
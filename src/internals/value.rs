@@ -1,4 +1,6 @@
+use std::collections::HashSet;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::{Hocon, HoconLoaderConfig};
 
@@ -6,16 +8,18 @@ use super::intermediate::{Child, HoconIntermediate, KeyType, Node};
 
 #[derive(Clone, Debug)]
 pub(crate) enum HoconValue {
-    Real(f64),
+    Real(f64, Option<String>),
     Integer(i64),
-    String(String),
-    UnquotedString(String),
+    String(Arc<str>),
+    UnquotedString(Arc<str>),
     Boolean(bool),
     Concat(Vec<HoconValue>),
     PathSubstitution {
         target: Box<HoconValue>,
         optional: bool,
         original: Option<Box<HoconValue>>,
+        // inline default, from `${target:-default}`
+        default: Option<Box<HoconValue>>,
     },
     PathSubstitutionInParent(Box<HoconValue>),
     ToConcatToArray {
@@ -33,11 +37,20 @@ pub(crate) enum HoconValue {
     EmptyObject,
     // Placeholder for an empty array
     EmptyArray,
+    // Marker for the start of an array, to concatenate it to a previous definition
+    ArrayStart,
+    // Marker for the start of an object, to replace a previous definition instead of merging
+    ObjectStart,
     Included {
         value: Box<HoconValue>,
         include_root: Option<Vec<HoconValue>>,
         original_path: Vec<HoconValue>,
+        // the included file or url, to keep track of where a value was defined
+        source: String,
     },
+    // Marker for the start of a new loaded document, with a label to keep track of
+    // where a value was defined
+    Source(String),
 }
 
 impl HoconValue {
@@ -64,12 +77,11 @@ impl HoconValue {
 
     pub(crate) fn to_path(&self) -> Vec<HoconValue> {
         match self {
-            HoconValue::UnquotedString(s) if s == "." => vec![],
+            HoconValue::UnquotedString(s) if &**s == "." => vec![],
             HoconValue::UnquotedString(s) => s
                 .trim()
                 .split('.')
-                .map(String::from)
-                .map(HoconValue::String)
+                .map(|s| HoconValue::String(s.into()))
                 .collect(),
             HoconValue::String(s) => vec![HoconValue::String(s.clone())],
             HoconValue::Concat(values) => values.iter().flat_map(HoconValue::to_path).collect(),
@@ -77,6 +89,151 @@ impl HoconValue {
         }
     }
 
+    // Label of the document where this value was defined, if it was included
+    pub(crate) fn included_source(&self) -> Option<&str> {
+        match self {
+            HoconValue::Included { value, source, .. } => {
+                value.included_source().or(Some(source.as_str()))
+            }
+            _ => None,
+        }
+    }
+
+    // Text of a real number as written in the document, if it was kept
+    pub(crate) fn number_text(&self) -> Option<&str> {
+        match self {
+            HoconValue::Real(_, text) => text.as_deref(),
+            HoconValue::Included { value, .. } | HoconValue::ToConcatToArray { value, .. } => {
+                value.number_text()
+            }
+            _ => None,
+        }
+    }
+
+    // Replace every string by the equal one already in `strings` so that they share the
+    // same allocation, or add it to `strings` if it's the first one
+    pub(crate) fn intern_strings(&mut self, strings: &mut HashSet<Arc<str>>) {
+        match self {
+            HoconValue::String(s) | HoconValue::UnquotedString(s) => match strings.get(s) {
+                Some(interned) => *s = Arc::clone(interned),
+                None => {
+                    strings.insert(Arc::clone(s));
+                }
+            },
+            HoconValue::Concat(values) => values
+                .iter_mut()
+                .for_each(|value| value.intern_strings(strings)),
+            HoconValue::PathSubstitution {
+                target,
+                original,
+                default,
+                ..
+            } => {
+                target.intern_strings(strings);
+                original
+                    .iter_mut()
+                    .chain(default.iter_mut())
+                    .for_each(|value| value.intern_strings(strings));
+            }
+            HoconValue::PathSubstitutionInParent(target) => target.intern_strings(strings),
+            HoconValue::ToConcatToArray {
+                value,
+                original_path,
+                ..
+            } => {
+                value.intern_strings(strings);
+                original_path
+                    .iter_mut()
+                    .for_each(|path_item| path_item.intern_strings(strings));
+            }
+            HoconValue::Included {
+                value,
+                include_root,
+                original_path,
+                ..
+            } => {
+                value.intern_strings(strings);
+                include_root
+                    .iter_mut()
+                    .flatten()
+                    .chain(original_path.iter_mut())
+                    .for_each(|path_item| path_item.intern_strings(strings));
+            }
+            _ => (),
+        }
+    }
+
+    // Replace the ids of items and of array indexes with `new_id`, called with the old id
+    pub(crate) fn renew_item_ids(&mut self, new_id: &mut impl FnMut(&str) -> String) {
+        match self {
+            // array indexes with an id are written `id-index`
+            HoconValue::Null(index) => {
+                if let Some(separator) = index.find('-') {
+                    *index = format!("{}{}", new_id(&index[..separator]), &index[separator..]);
+                }
+            }
+            HoconValue::ToConcatToArray {
+                value,
+                original_path,
+                item_id,
+            } => {
+                *item_id = new_id(item_id);
+                value.renew_item_ids(new_id);
+                original_path
+                    .iter_mut()
+                    .for_each(|path_item| path_item.renew_item_ids(new_id));
+            }
+            HoconValue::Included {
+                value,
+                include_root,
+                original_path,
+                ..
+            } => {
+                value.renew_item_ids(new_id);
+                include_root
+                    .iter_mut()
+                    .flatten()
+                    .chain(original_path.iter_mut())
+                    .for_each(|path_item| path_item.renew_item_ids(new_id));
+            }
+            _ => (),
+        }
+    }
+
+    // Replace the marker for the start of an object by an empty object if `replace` is set,
+    // or remove it otherwise
+    pub(crate) fn replace_object_start(self, replace: bool) -> Option<HoconValue> {
+        match self {
+            HoconValue::ObjectStart if replace => Some(HoconValue::EmptyObject),
+            HoconValue::ObjectStart => None,
+            HoconValue::Included {
+                value,
+                include_root,
+                original_path,
+                source,
+            } => value
+                .replace_object_start(replace)
+                .map(|value| HoconValue::Included {
+                    value: Box::new(value),
+                    include_root,
+                    original_path,
+                    source,
+                }),
+            HoconValue::ToConcatToArray {
+                value,
+                original_path,
+                item_id,
+            } => value
+                .replace_object_start(replace)
+                .map(|value| HoconValue::ToConcatToArray {
+                    value: Box::new(value),
+                    original_path,
+                    item_id,
+                }),
+            value => Some(value),
+        }
+    }
+
     pub(crate) fn finalize(
         self,
         root: &HoconIntermediate,
@@ -90,27 +247,31 @@ impl HoconValue {
             HoconValue::BadValue(err) => Ok(public_bad_value_or_err!(config, err)),
             HoconValue::Boolean(b) => Ok(Hocon::Boolean(b)),
             HoconValue::Integer(i) => Ok(Hocon::Integer(i)),
-            HoconValue::Real(f) => Ok(Hocon::Real(f)),
-            HoconValue::String(s) => Ok(Hocon::String(s)),
-            HoconValue::UnquotedString(ref s) if s == "null" => Ok(Hocon::Null),
+            HoconValue::Real(f, _) => Ok(Hocon::Real(f)),
+            HoconValue::String(s) => Ok(Hocon::String(s.to_string())),
+            HoconValue::UnquotedString(ref s) if &**s == "null" => Ok(Hocon::Null),
             HoconValue::UnquotedString(s) => {
                 if in_concat {
-                    Ok(Hocon::String(s))
+                    Ok(Hocon::String(s.to_string()))
                 } else {
                     Ok(Hocon::String(String::from(s.trim())))
                 }
             }
-            HoconValue::Concat(values) => Ok(Hocon::String({
+            HoconValue::Concat(values) => {
                 let nb_items = values.len();
-                values
+                let values = values
                     .into_iter()
                     .enumerate()
                     .map(|item| match item {
                         (0, HoconValue::UnquotedString(s)) => {
-                            HoconValue::UnquotedString(String::from(s.trim_start()))
+                            HoconValue::UnquotedString(s.trim_start().into())
                         }
                         (i, HoconValue::UnquotedString(ref s)) if i == nb_items - 1 => {
-                            HoconValue::UnquotedString(String::from(s.trim_end()))
+                            HoconValue::UnquotedString(s.trim_end().into())
+                        }
+                        // a number is concatenated with the text it was written with
+                        (_, HoconValue::Real(_, Some(text))) => {
+                            HoconValue::UnquotedString(text.into())
                         }
                         (_, v) => v,
                     })
@@ -123,14 +284,31 @@ impl HoconValue {
                             substituting_path.clone(),
                         )
                     })
-                    .filter_map(|v| v.ok().and_then(|v| v.as_internal_string()))
-                    .collect::<Vec<String>>()
-                    .join("")
-            })),
+                    .collect::<Vec<_>>();
+                if config.partial_substitutions {
+                    // a pending substitution makes the whole concatenation pending
+                    if let Some(pending) = values.iter().find_map(|v| match v {
+                        Ok(pending @ Hocon::BadValue(crate::Error::KeyNotFound { .. })) => {
+                            Some(pending.clone())
+                        }
+                        _ => None,
+                    }) {
+                        return Ok(pending);
+                    }
+                }
+                Ok(Hocon::String(
+                    values
+                        .into_iter()
+                        .filter_map(|v| v.ok().and_then(|v| v.as_internal_string()))
+                        .collect::<Vec<String>>()
+                        .join(""),
+                ))
+            }
             HoconValue::PathSubstitution {
                 target: v,
                 optional,
                 original,
+                default,
             } => {
                 // second pass for substitution
                 let fixed_up_path = if let Some(included_path) = included_path.clone() {
@@ -145,31 +323,49 @@ impl HoconValue {
                     v.to_path()
                 };
                 if Some(fixed_up_path.clone()) == substituting_path {
+                    root.depends_on_context.set(true);
                     Ok(Hocon::Null)
                 } else {
                     match (
                         config.strict,
-                        config.system,
-                        root.tree
-                            .find_key(config, fixed_up_path.clone())
-                            .and_then(|v| {
-                                v.finalize(root, config, included_path, Some(fixed_up_path))
-                            }),
+                        config.system || config.substitution_source.is_some(),
+                        root.finalize_substitution(config, fixed_up_path, included_path.clone()),
                     ) {
-                        (_, true, Err(err)) | (_, true, Ok(Hocon::BadValue(err))) => {
-                            match (
-                                std::env::var(
-                                    v.to_path()
-                                        .into_iter()
-                                        .map(HoconValue::string_value)
-                                        .collect::<Vec<_>>()
-                                        .join("."),
-                                ),
-                                optional,
-                                original,
-                            ) {
-                                (Ok(val), _, _) => Ok(Hocon::String(val)),
+                        (_, external, Err(err)) | (_, external, Ok(Hocon::BadValue(err)))
+                            if external || optional || default.is_some() =>
+                        {
+                            let keys = v
+                                .to_path()
+                                .into_iter()
+                                .map(HoconValue::string_value)
+                                .collect::<Vec<_>>();
+                            let from_source = config
+                                .substitution_source
+                                .as_ref()
+                                .and_then(|source| source.get(&keys.join(".")).cloned());
+                            let from_env = || {
+                                if config.system {
+                                    let prefix = config.env_prefix.as_deref().unwrap_or("");
+                                    let name = keys.join(&config.env_separator);
+                                    std::env::var(format!("{}{}", prefix, name)).ok()
+                                } else {
+                                    None
+                                }
+                            };
+                            match (from_source.or_else(from_env), optional, original) {
+                                (Some(val), _, _) => Ok(Hocon::String(val)),
+                                _ if default.is_some() => default
+                                    .expect("default was checked to be present")
+                                    .finalize(
+                                        root,
+                                        config,
+                                        in_concat,
+                                        included_path,
+                                        substituting_path,
+                                    ),
                                 (_, true, Some(val)) => val.simple_finalize(),
+                                // an optional substitution to an undefined value is not an error
+                                (_, true, None) => Ok(Hocon::Null),
                                 _ => Ok(public_bad_value_or_err!(config, err)),
                             }
                         }
@@ -188,6 +384,9 @@ impl HoconValue {
             HoconValue::Temp => unreachable!(),
             HoconValue::EmptyObject => unreachable!(),
             HoconValue::EmptyArray => unreachable!(),
+            HoconValue::ArrayStart => unreachable!(),
+            HoconValue::ObjectStart => unreachable!(),
+            HoconValue::Source(_) => unreachable!(),
             HoconValue::PathSubstitutionInParent(_) => unreachable!(),
             HoconValue::ToConcatToArray { .. } => unreachable!(),
         }
@@ -201,9 +400,9 @@ impl HoconValue {
             HoconValue::Null(_) => Ok(Hocon::Null),
             HoconValue::Boolean(b) => Ok(Hocon::Boolean(b)),
             HoconValue::Integer(i) => Ok(Hocon::Integer(i)),
-            HoconValue::Real(f) => Ok(Hocon::Real(f)),
-            HoconValue::String(s) => Ok(Hocon::String(s)),
-            HoconValue::UnquotedString(ref s) if s == "null" => Ok(Hocon::Null),
+            HoconValue::Real(f, _) => Ok(Hocon::Real(f)),
+            HoconValue::String(s) => Ok(Hocon::String(s.to_string())),
+            HoconValue::UnquotedString(ref s) if &**s == "null" => Ok(Hocon::Null),
             HoconValue::UnquotedString(s) => Ok(Hocon::String(String::from(s.trim()))),
             _ => unimplemented!(),
         }
@@ -211,8 +410,8 @@ impl HoconValue {
 
     pub(crate) fn string_value(self) -> String {
         match self {
-            HoconValue::String(s) => s,
-            HoconValue::UnquotedString(s) => s,
+            HoconValue::String(s) => s.to_string(),
+            HoconValue::UnquotedString(s) => s.to_string(),
             HoconValue::Null(_) => String::from("null"),
             HoconValue::Integer(i) => i.to_string(),
             _ => unreachable!(),
@@ -230,6 +429,7 @@ impl HoconValue {
                 target: path,
                 optional,
                 original,
+                default,
             } => {
                 match current_tree.find_key(config, path.to_path()) {
                     Err(_) | Ok(Node::Leaf(HoconValue::BadValue(_))) => {
@@ -238,6 +438,19 @@ impl HoconValue {
                             target: path,
                             optional,
                             original,
+                            default,
+                        }))
+                    }
+                    Ok(Node::Leaf(HoconValue::PathSubstitution { target, .. }))
+                        if target.to_path() == at_path =>
+                    {
+                        // The target is waiting for a substitution to this path, keep the
+                        // substitution so that the cycle is found on second pass
+                        Ok(Node::Leaf(HoconValue::PathSubstitution {
+                            target: path,
+                            optional,
+                            original,
+                            default,
                         }))
                     }
                     Ok(v) => Ok(v.deep_clone()),
@@ -313,6 +526,7 @@ impl HoconValue {
                 value,
                 original_path,
                 include_root,
+                source,
             } => {
                 match *value.clone() {
                     HoconValue::PathSubstitution { target: path, .. }
@@ -340,6 +554,7 @@ impl HoconValue {
                                     value: Box::new(value),
                                     original_path: original_path.clone(),
                                     include_root: include_root.clone(),
+                                    source: source.clone(),
                                 })
                                 .collect(),
                         )
@@ -355,6 +570,7 @@ impl HoconValue {
                             value: Box::new(value_found),
                             original_path,
                             include_root,
+                            source,
                         }))
                     }
                     v => v,
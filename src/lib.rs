@@ -145,7 +145,8 @@
 //!
 //! # Features
 //!
-//! All features are enabled by default. They can be disabled to reduce dependencies.
+//! All features except `encoding` and `watch` are enabled by default. They can be disabled
+//! to reduce dependencies.
 //!
 //! ### `url-support`
 //!
@@ -156,7 +157,9 @@
 //!
 //! ### `serde-support`
 //!
-//! This feature enable deserializing to a `struct` implementing `Deserialize` using `serde`
+//! This feature enable deserializing to a `struct` implementing `Deserialize` using `serde`,
+//! serializing a `Hocon` document with any `serde` serializer, and loading a
+//! `serde_json::Value` as a document
 //!
 //! ```rust
 //! use serde::Deserialize;
@@ -179,24 +182,65 @@
 //! # }
 //!  ```
 //!
+//! ### `encoding`
+//!
+//! This feature, disabled by default, enable including files that are not encoded in UTF-8,
+//! by giving their encoding with `include file("path/to/legacy.conf", "latin1")`. If
+//! disabled, only UTF-8 is supported.
+//!
+//! ### `watch`
+//!
+//! This feature, disabled by default, enable watching loaded files and their includes for
+//! changes, receiving the updated document with `HoconLoader::watch`.
+//!
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 mod internals;
+mod macros;
 mod parser;
 mod value;
-pub use value::Hocon;
+pub use value::{FromHocon, Hocon};
 mod error;
 pub use error::Error;
 pub(crate) mod helper;
 mod loader_config;
 pub(crate) use loader_config::*;
+pub use loader_config::{FileCache, FileType, MergeStrategy};
 
 #[cfg(feature = "serde-support")]
 mod serde;
 #[cfg(feature = "serde-support")]
 pub use crate::serde::de;
 
+#[cfg(feature = "watch")]
+mod watch;
+#[cfg(feature = "watch")]
+pub use watch::HoconChanges;
+
+/// Load an HOCON file with the default options. This is a shortcut for
+/// `HoconLoader::new().load_file(path)?.hocon()`, use [`HoconLoader`](struct.HoconLoader.html)
+/// to change the options.
+///
+/// ```rust
+/// # use hocon::{Hocon, Error};
+/// # fn main() -> Result<(), Error> {
+/// let doc = hocon::from_file("tests/data/basic.conf")?;
+/// assert_eq!(doc["a"], Hocon::Integer(5));
+/// assert_eq!(doc["f"]["g"], Hocon::Boolean(false));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// * Any error that would be returned by [`HoconLoader::load_file`](struct.HoconLoader.html#method.load_file)
+///   or [`HoconLoader::hocon`](struct.HoconLoader.html#method.hocon)
+pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Hocon, Error> {
+    HoconLoader::new().load_file(path)?.hocon()
+}
+
 /// Helper to load an HOCON file. This is used to set up the HOCON loader's option,
 /// like strict mode, disabling system environment, and to buffer several documents.
 ///
@@ -233,6 +277,7 @@ pub use crate::serde::de;
 pub struct HoconLoader {
     config: HoconLoaderConfig,
     internal: internals::HoconInternal,
+    loaded_files: Vec<LoadedFile>,
 }
 
 impl Default for HoconLoader {
@@ -247,6 +292,7 @@ impl HoconLoader {
         Self {
             config: HoconLoaderConfig::default(),
             internal: internals::HoconInternal::empty(),
+            loaded_files: vec![],
         }
     }
 
@@ -299,6 +345,95 @@ impl HoconLoader {
         }
     }
 
+    /// Only substitute from environment variables starting with `prefix`. The substitution
+    /// `${FOO}` will then be resolved from the environment variable `{prefix}FOO`
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// # std::env::set_var("MYAPP_PORT", "8080");
+    /// assert_eq!(
+    ///     HoconLoader::new()
+    ///         .with_env_prefix("MYAPP_")
+    ///         .load_str(r#"{ port: ${PORT} }"#)?
+    ///         .hocon()?["port"],
+    ///     Hocon::String(String::from("8080"))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_env_prefix(&self, prefix: &str) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                env_prefix: Some(String::from(prefix)),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Set the separator used between keys of a substitution path to find the environment
+    /// variable to substitute, by default `.`. With the separator `__`, the substitution
+    /// `${db.max_conn}` will be resolved from the environment variable `db__max_conn`, and
+    /// keys can keep their `_`
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// # std::env::set_var("MYAPP__db__max_conn", "10");
+    /// assert_eq!(
+    ///     HoconLoader::new()
+    ///         .with_env_prefix("MYAPP__")
+    ///         .env_separator("__")
+    ///         .load_str(r#"{ max: ${db.max_conn} }"#)?
+    ///         .hocon()?["max"],
+    ///     Hocon::String(String::from("10"))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn env_separator(&self, separator: &str) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                env_separator: String::from(separator),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Resolve substitutions not found in the document from `source` before looking in the
+    /// system environment. This is done even when the system environment is disabled with
+    /// [`no_system`](struct.HoconLoader.html#method.no_system), to get reproducible
+    /// substitutions
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # use std::collections::HashMap;
+    /// # fn main() -> Result<(), Error> {
+    /// let mut source = HashMap::new();
+    /// source.insert(String::from("HOME"), String::from("/home/test"));
+    /// assert_eq!(
+    ///     HoconLoader::new()
+    ///         .no_system()
+    ///         .with_substitution_source(source)
+    ///         .load_str(r#"{ home: ${HOME} }"#)?
+    ///         .hocon()?["home"],
+    ///     Hocon::String(String::from("/home/test"))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_substitution_source(&self, source: HashMap<String, String>) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                substitution_source: Some(source),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
     /// Disable loading included files from external urls.
     ///
     /// # Example HOCON document
@@ -345,6 +480,102 @@ impl HoconLoader {
         }
     }
 
+    /// Disable including local files, with `include "file.conf"`, `include file("file.conf")`,
+    /// `include classpath("file.conf")` or `include url("file:///file.conf")`. The file system
+    /// is not accessed, and an include is replaced by a
+    /// [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) with an
+    /// [`Error::Include`](enum.Error.html#variant.Include), or returns this error in strict mode
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .no_file_include()
+    ///     .load_file("tests/data/include_file.conf")?
+    ///     .hocon()?;
+    /// assert_eq!(doc["a"], Hocon::Integer(12));
+    /// assert_eq!(doc["b"], Hocon::BadValue(Error::MissingKey));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn no_file_include(&self) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                file_include: false,
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Disable all includes, of local files as with
+    /// [`no_file_include`](struct.HoconLoader.html#method.no_file_include) and of URLs as
+    /// with `no_url_include`, to load untrusted documents
+    pub fn no_includes(&self) -> Self {
+        let loader = self.no_file_include();
+        #[cfg(feature = "url-support")]
+        let loader = loader.no_url_include();
+        loader
+    }
+
+    /// Set how to find the file for an `include classpath("name.conf")`. The resolver gets the
+    /// name of the classpath resource, and returns the path of the file to include, or `None`
+    /// if it can't be found. Without a resolver, classpath includes are missing
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .with_classpath_resolver(|name| Some(std::path::Path::new("tests/data").join(name)))
+    ///     .load_file("tests/data/include_classpath.conf")?
+    ///     .hocon()?;
+    /// assert_eq!(doc["a"], Hocon::Integer(5));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_classpath_resolver<F>(&self, resolver: F) -> Self
+    where
+        F: Fn(&str) -> Option<PathBuf> + Send + Sync + 'static,
+    {
+        Self {
+            config: HoconLoaderConfig {
+                classpath_resolver: Some(ClasspathResolver(std::sync::Arc::new(resolver))),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Share a cache of the documents parsed from files with other loaders, to avoid reading
+    /// and parsing again files that didn't change when loading documents several times, for
+    /// example on configuration reload. Files are read and parsed again when their
+    /// modification time, or the one of a file they include, changes
+    ///
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use hocon::{Hocon, HoconLoader, Error, FileCache};
+    /// # fn main() -> Result<(), Error> {
+    /// let cache = Arc::new(FileCache::new());
+    /// for _ in 0..2 {
+    ///     let doc = HoconLoader::new()
+    ///         .with_shared_cache(cache.clone())
+    ///         .load_file("tests/data/include.conf")?
+    ///         .hocon()?;
+    ///     assert_eq!(doc["a"], Hocon::Integer(5));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_shared_cache(&self, cache: std::sync::Arc<FileCache>) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                file_cache: Some(cache),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
     /// Sets the HOCON loader to return the first [`Error`](enum.Error.html) encoutered instead
     /// of wrapping it in a [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) and
     /// continuing parsing
@@ -392,6 +623,30 @@ impl HoconLoader {
         }
     }
 
+    /// Only fail on include errors: a missing or invalid include will return an `Err`, while
+    /// other errors like missing substitutions will still be replaced by a `BadValue`
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// # let example = r#"{ a = ${b} }"#;
+    /// assert_eq!(
+    ///     HoconLoader::new().strict_include().load_str(example)?.hocon()?["a"],
+    ///     Hocon::BadValue(Error::KeyNotFound { key: String::from("b") })
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn strict_include(&self) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                strict_include: true,
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
     /// Set a new maximum include depth, by default 10
     pub fn max_include_depth(&self, new_max_depth: u8) -> Self {
         Self {
@@ -403,95 +658,1059 @@ impl HoconLoader {
         }
     }
 
-    pub(crate) fn load_from_str_of_conf_file(self, s: FileRead) -> Result<Self, Error> {
-        Ok(Self {
-            internal: self.internal.add(self.config.parse_str_to_internal(s)?),
-            config: self.config,
-        })
+    /// Set a new maximum depth of nested substitutions, by default 32. A substitution that
+    /// needs more nested substitutions to be resolved, for example because it is part of a
+    /// cycle like `a = ${b}, b = ${a}`, is an
+    /// [`Error::SubstitutionCycle`](enum.Error.html#variant.SubstitutionCycle)
+    pub fn max_substitution_depth(&self, new_max_depth: u8) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                max_substitution_depth: new_max_depth,
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
     }
 
-    /// Load a string containing an `Hocon` document. Includes are not supported when
-    /// loading from a string
-    ///
-    /// # Errors
-    ///
-    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
+    /// Set a maximum number of keys and array elements in the final document, counting
+    /// the nested ones. By default there is no limit. The limit is checked while the
+    /// documents are merged, stopping as soon as it is exceeded, and again once substitutions
+    /// are resolved as they can copy objects
     ///
-    /// # Additional errors in strict mode
+    /// # Example
     ///
-    /// * [`Error::IncludeNotAllowedFromStr`](enum.Error.html#variant.IncludeNotAllowedFromStr)
-    /// if there is an include in the string
-    pub fn load_str(self, s: &str) -> Result<Self, Error> {
-        self.load_from_str_of_conf_file(FileRead {
-            hocon: Some(String::from(s)),
-            ..Default::default()
-        })
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// let doc = HoconLoader::new()
+    ///     .max_keys(2)
+    ///     .load_str(r#"{ a: [1, 2] }"#)
+    ///     .and_then(|loader| loader.hocon());
+    /// assert_eq!(doc, Err(Error::TooManyKeys));
+    /// ```
+    pub fn max_keys(&self, new_max_keys: usize) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                max_keys: Some(new_max_keys),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
     }
 
-    /// Load the HOCON configuration file containing an `Hocon` document
-    ///
-    /// # Errors
-    ///
-    /// * [`Error::File`](enum.Error.html#variant.File) if there was an error reading the
-    /// file content
-    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
-    ///
-    /// # Additional errors in strict mode
+    /// Keep the original text of real numbers, like `1.0` or `1e3`. They are still loaded as a
+    /// [`Hocon::Real`](enum.Hocon.html#variant.Real), and their text is available with
+    /// [`number_texts`](struct.HoconLoader.html#method.number_texts).
     ///
-    /// * [`Error::TooManyIncludes`](enum.Error.html#variant.TooManyIncludes)
-    /// if there are too many included files within included files. The limit can be
-    /// changed with [`max_include_depth`](struct.HoconLoader.html#method.max_include_depth)
-    pub fn load_file<P: AsRef<Path>>(&self, path: P) -> Result<Self, Error> {
-        let mut file_path = path.as_ref().to_path_buf();
-        // pub fn load_file(&self, path: &str) -> Result<Self, Error> {
-        // let mut file_path = Path::new(path).to_path_buf();
-        if !file_path.has_root() {
-            let mut current_path = std::env::current_dir().map_err(|_| Error::File {
-                path: String::from(path.as_ref().to_str().unwrap_or("invalid path")),
-            })?;
-            current_path.push(path.as_ref());
-            file_path = current_path;
-        }
-        let conf = self.config.with_file(file_path);
-        let contents = conf.read_file().map_err(|err| {
-            let path = match err {
-                Error::File { path } => path,
-                Error::Include { path } => path,
-                Error::Io { message } => message,
-                _ => "unmatched error".to_string(),
-            };
-            Error::File { path }
-        })?;
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// # let example = r#"{ a = 1e3 }"#;
+    /// let loader = HoconLoader::new().preserve_number_text().load_str(example)?;
+    /// let doc = loader.clone().hocon()?;
+    /// assert_eq!(doc["a"], Hocon::Real(1000.0));
+    /// assert_eq!(loader.number_texts()?["a"], "1e3");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn preserve_number_text(&self) -> Self {
         Self {
-            config: conf,
+            config: HoconLoaderConfig {
+                preserve_number_text: true,
+                ..self.config.clone()
+            },
             ..self.clone()
         }
-        .load_from_str_of_conf_file(contents)
     }
 
-    /// Load the documents as HOCON
+    /// Share the content of equal strings while loading documents, so that each distinct key
+    /// or string value is allocated only once until the document is finalized. This lowers
+    /// peak memory use for documents repeating the same keys or values many times, like
+    /// enum-like tags or arrays of objects. The resulting document is the same as without
+    /// this option.
     ///
-    /// # Errors in strict mode
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .intern_strings()
+    ///     .load_str(r#"{ a = blue, b = blue }"#)?
+    ///     .hocon()?;
+    /// assert_eq!(doc["a"], Hocon::String(String::from("blue")));
+    /// assert_eq!(doc["b"], Hocon::String(String::from("blue")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn intern_strings(&self) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                intern_strings: true,
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Nest the document under `key` when its root is not an object, like an array or a
+    /// single value, so that it can be accessed with `doc[key]` or deserialized to a
+    /// struct with a field named `key`. Documents whose root is an object are not affected.
     ///
-    /// * [`Error::Include`](enum.Error.html#variant.Include) if there was an issue with an
-    /// included file
-    /// * [`Error::KeyNotFound`](enum.Error.html#variant.KeyNotFound) if there is a substitution
-    /// with a key that is not present in the document
-    /// * [`Error::DisabledExternalUrl`](enum.Error.html#variant.DisabledExternalUrl) if crate
-    /// was built without feature `url-support` and an `include url("...")` was found
-    pub fn hocon(self) -> Result<Hocon, Error> {
-        let config = &self.config;
-        self.internal.merge(config)?.finalize(config)
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().root_key("items").load_str("[1, 2, 3]")?.hocon()?;
+    /// assert_eq!(doc["items"][0], Hocon::Integer(1));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn root_key(&self, key: &str) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                root_key: Some(String::from(key)),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
     }
 
-    /// Deserialize the loaded documents to the target type
+    /// Disable [automatic type conversions](https://github.com/lightbend/config/blob/master/HOCON.md#automatic-type-conversions)
+    /// when deserializing with [`resolve`](struct.HoconLoader.html#method.resolve). Values
+    /// must already be of the expected type: a string like `"80"` can't be deserialized to a
+    /// number, and `yes` can't be deserialized to a `bool`.
     ///
-    /// # Errors
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// #[derive(Deserialize)]
+    /// struct Server {
+    ///     port: u16,
+    /// }
     ///
-    /// * [`Error::Deserialization`](enum.Error.html#variant.Deserialization) if there was a
-    /// serde error during deserialization (missing required field, type issue, ...)
+    /// # #[cfg(feature = "serde-support")]
+    /// assert!(HoconLoader::new()
+    ///     .strict_types()
+    ///     .load_str(r#"{ port = "80" }"#)?
+    ///     .resolve::<Server>()
+    ///     .is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde-support")]
+    pub fn strict_types(&self) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                strict_types: true,
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Mark keys as deprecated. Each entry of `mapping` goes from a deprecated key to its
+    /// replacement, with keys separated by `.` for nested values. When a deprecated key is
+    /// present in the document, a warning is returned by
+    /// [`hocon_with_warnings`](struct.HoconLoader.html#method.hocon_with_warnings), and its
+    /// value is copied to the replacement key if it is not already set.
     ///
-    /// # Additional errors in strict mode
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let mut mapping = HashMap::new();
+    /// mapping.insert(String::from("server.host"), String::from("server.address"));
+    ///
+    /// let (doc, warnings) = HoconLoader::new()
+    ///     .deprecate_keys(mapping)
+    ///     .load_str(r#"{ server.host = localhost }"#)?
+    ///     .hocon_with_warnings()?;
+    /// assert_eq!(doc["server"]["address"].as_string(), Some(String::from("localhost")));
+    /// assert_eq!(warnings.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deprecate_keys(&self, mapping: HashMap<String, String>) -> Self {
+        let mut deprecated_keys = self.config.deprecated_keys.clone();
+        deprecated_keys.extend(mapping);
+        deprecated_keys.sort();
+        Self {
+            config: HoconLoaderConfig {
+                deprecated_keys,
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Select a profile from the `profiles` object of the document. Once loaded, the values
+    /// of `profiles.<name>` are merged over the rest of the document, as with
+    /// [`Hocon::merge`](enum.Hocon.html#method.merge), and the `profiles` key is removed.
+    /// If the profile doesn't exist, only the `profiles` key is removed.
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .with_profile("prod")
+    ///     .load_str(r#"{
+    ///         db.host = localhost
+    ///         profiles {
+    ///             prod { db.host = db.example.com }
+    ///         }
+    ///     }"#)?
+    ///     .hocon()?;
+    /// assert_eq!(doc["db"]["host"].as_string(), Some(String::from("db.example.com")));
+    /// assert_eq!(doc["profiles"], Hocon::BadValue(Error::MissingKey));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_profile(&self, name: &str) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                profile: Some(String::from(name)),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Allow keys and values separated by spaces only, without `:` or `=`, like in
+    /// `nameserver 8.8.8.8`. The key must be unquoted. This is only tried after the other
+    /// forms, so `key = value`, `key: value` and `key { ... }` keep their meaning, and a
+    /// key followed by an array, as in `key [1, 2]`, is set to that array.
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .allow_space_separator()
+    ///     .load_str("nameserver 8.8.8.8\noptions { timeout 2 }")?
+    ///     .hocon()?;
+    /// assert_eq!(doc["nameserver"].as_string(), Some(String::from("8.8.8.8")));
+    /// assert_eq!(doc["options"]["timeout"].as_i64(), Some(2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn allow_space_separator(&self) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                allow_space_separator: true,
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Allow the given characters in unquoted strings. By default, characters such as `@`,
+    /// `*` or `!` are forbidden in unquoted strings by the HOCON specifications and end them.
+    /// Allowing a character used by the HOCON syntax itself, such as `{` or `=`, will
+    /// prevent parsing documents using it.
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .unquoted_string_allow("@")
+    ///     .load_str("user = me@example")?
+    ///     .hocon()?;
+    /// assert_eq!(doc["user"].as_string(), Some(String::from("me@example")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unquoted_string_allow(&self, chars: &str) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                unquoted_string_stop: self
+                    .config
+                    .unquoted_string_stop
+                    .iter()
+                    .filter(|stop| !chars.contains(**stop))
+                    .cloned()
+                    .collect(),
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Choose which format wins when a file is loaded without an extension and several files
+    /// with this base name exist. All the formats found are loaded, by default in order
+    /// properties, JSON then HOCON, each overriding the values of the previous ones. The
+    /// preferred format is loaded last, the other ones keeping this order
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error, FileType};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .load_file("tests/data/file_with_different_types")?
+    ///     .hocon()?;
+    /// assert_eq!(doc["common"].as_string(), Some(String::from("hocon")));
+    ///
+    /// let doc = HoconLoader::new()
+    ///     .preferred_file_type(FileType::Json)
+    ///     .load_file("tests/data/file_with_different_types")?
+    ///     .hocon()?;
+    /// assert_eq!(doc["common"].as_string(), Some(String::from("json")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn preferred_file_type(&self, file_type: FileType) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                preferred_file_type: file_type,
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Choose how values are merged when a key is defined several times. See
+    /// [`MergeStrategy`](enum.MergeStrategy.html) for the available strategies, the default
+    /// being [`MergeStrategy::ObjectMerge`](enum.MergeStrategy.html#variant.ObjectMerge) as
+    /// described in the HOCON specifications.
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error, MergeStrategy};
+    /// # fn main() -> Result<(), Error> {
+    /// # let example = r#"{ a { x = 1 }, a { y = 2 } }"#;
+    /// let doc = HoconLoader::new().load_str(example)?.hocon()?;
+    /// assert_eq!(doc["a"]["x"], Hocon::Integer(1));
+    ///
+    /// let doc = HoconLoader::new()
+    ///     .merge_strategy(MergeStrategy::ReplaceAll)
+    ///     .load_str(example)?
+    ///     .hocon()?;
+    /// assert_eq!(doc["a"]["x"], Hocon::BadValue(Error::MissingKey));
+    /// assert_eq!(doc["a"]["y"], Hocon::Integer(2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge_strategy(&self, strategy: MergeStrategy) -> Self {
+        Self {
+            config: HoconLoaderConfig {
+                merge_strategy: strategy,
+                ..self.config.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn load_from_str_of_conf_file(
+        self,
+        s: FileRead,
+        source: String,
+    ) -> Result<Self, Error> {
+        let parsed = self.config.parse_str_to_internal(s)?;
+        Ok(self.load_parsed(parsed, source))
+    }
+
+    fn load_parsed(self, parsed: internals::HoconInternal, source: String) -> Self {
+        Self {
+            internal: self
+                .internal
+                .add(internals::HoconInternal::from_source(source))
+                .add(parsed),
+            config: self.config,
+            loaded_files: self.loaded_files,
+        }
+    }
+
+    /// Load a string containing an `Hocon` document. Includes are not supported when
+    /// loading from a string
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
+    ///
+    /// # Additional errors in strict mode
+    ///
+    /// * [`Error::IncludeNotAllowedFromStr`](enum.Error.html#variant.IncludeNotAllowedFromStr)
+    /// if there is an include in the string
+    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the end of the document could
+    ///   not be parsed, with the line and column where parsing stopped. When not in strict
+    ///   mode, the document is loaded up to that point and the rest of it is ignored
+    pub fn load_str(self, s: &str) -> Result<Self, Error> {
+        self.load_from_str_of_conf_file(
+            FileRead {
+                hocon: Some(String::from(s)),
+                ..Default::default()
+            },
+            String::from("string"),
+        )
+    }
+
+    /// Load a string containing a document in the given format, for documents that are
+    /// not read from a file with an extension giving their format. Includes are not
+    /// supported when loading from a string
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error, FileType};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .load_str_as("a.b=some value", FileType::Properties)?
+    ///     .hocon()?;
+    /// assert_eq!(doc["a"]["b"].as_string(), Some(String::from("some value")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
+    ///
+    /// # Additional errors in strict mode
+    ///
+    /// * [`Error::IncludeNotAllowedFromStr`](enum.Error.html#variant.IncludeNotAllowedFromStr)
+    ///   if there is an include in the string
+    pub fn load_str_as(self, s: &str, file_type: FileType) -> Result<Self, Error> {
+        self.load_from_str_of_conf_file(
+            FileRead::from_file_type(&file_type, String::from(s)),
+            String::from("string"),
+        )
+    }
+
+    /// Load the HOCON configuration file containing an `Hocon` document. A path of `-` reads
+    /// the document from the standard input instead, as for a string includes are not
+    /// supported. For a path without an extension, the `.conf`, `.json` and `.properties`
+    /// files with this base name are all loaded, see
+    /// [`preferred_file_type`](struct.HoconLoader.html#method.preferred_file_type) for
+    /// which one wins
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::File`](enum.Error.html#variant.File) if there was an error reading the
+    /// file content
+    /// * [`Error::Io`](enum.Error.html#variant.Io) if there was an error reading the
+    ///   standard input
+    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
+    ///
+    /// # Additional errors in strict mode
+    ///
+    /// * [`Error::TooManyIncludes`](enum.Error.html#variant.TooManyIncludes)
+    /// if there are too many included files within included files. The limit can be
+    /// changed with [`max_include_depth`](struct.HoconLoader.html#method.max_include_depth)
+    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the end of the document could
+    ///   not be parsed, with the line and column where parsing stopped. When not in strict
+    ///   mode, the document is loaded up to that point and the rest of it is ignored
+    pub fn load_file<P: AsRef<Path>>(&self, path: P) -> Result<Self, Error> {
+        if path.as_ref() == Path::new("-") {
+            return self.clone().load_reader(std::io::stdin());
+        }
+        let mut file_path = path.as_ref().to_path_buf();
+        // pub fn load_file(&self, path: &str) -> Result<Self, Error> {
+        // let mut file_path = Path::new(path).to_path_buf();
+        if !file_path.has_root() {
+            let mut current_path = std::env::current_dir().map_err(|_| Error::File {
+                path: String::from(path.as_ref().to_str().unwrap_or("invalid path")),
+            })?;
+            current_path.push(path.as_ref());
+            file_path = current_path;
+        }
+        let source = path.as_ref().display().to_string();
+        let conf = HoconLoaderConfig {
+            include_chain: vec![source.clone()],
+            ..self.config.with_file(file_path)
+        };
+        let modified = conf.file_modified();
+        let parsed = Self::parse_loaded_file(&conf)?;
+        let start = self.internal.internal.len();
+        let mut loader = Self {
+            config: conf.clone(),
+            ..self.clone()
+        }
+        .load_parsed(parsed, source.clone());
+        loader.loaded_files.push(LoadedFile {
+            config: conf,
+            source,
+            range: start..loader.internal.internal.len(),
+            modified: std::sync::Mutex::new(modified),
+        });
+        Ok(loader)
+    }
+
+    fn parse_loaded_file(conf: &HoconLoaderConfig) -> Result<internals::HoconInternal, Error> {
+        conf.parse_file_cached(None, |conf| {
+            let contents = conf.read_file().map_err(|err| {
+                let path = match err {
+                    Error::File { path } => path,
+                    Error::Include { path, .. } => path,
+                    Error::Io { message } => message,
+                    _ => "unmatched error".to_string(),
+                };
+                Error::File { path }
+            })?;
+            conf.parse_str_to_internal(contents)
+        })
+    }
+
+    /// Read again the files loaded with [`load_file`](struct.HoconLoader.html#method.load_file)
+    /// if any of them changed since they were last read, checking their modification time.
+    /// The new document is returned, with the other loaded documents unchanged, or `None` if
+    /// no file changed
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let loader = HoconLoader::new().load_file("tests/data/basic.conf")?;
+    /// let doc = loader.clone().hocon()?;
+    /// // ... later, check if the file changed
+    /// if let Some(new_doc) = loader.reload()? {
+    ///     // use the updated document
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::File`](enum.Error.html#variant.File) if there was an error reading a file
+    /// * Any error that would be returned by [`hocon()`](struct.HoconLoader.html#method.hocon)
+    pub fn reload(&self) -> Result<Option<Hocon>, Error> {
+        let modified = self
+            .loaded_files
+            .iter()
+            .map(|file| file.config.file_modified())
+            .collect::<Vec<_>>();
+        let changed = self
+            .loaded_files
+            .iter()
+            .zip(modified.iter())
+            .any(|(file, modified)| {
+                *file
+                    .modified
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    != *modified
+            });
+        if !changed {
+            return Ok(None);
+        }
+
+        let hocon = self.read_loaded_files_again()?;
+        for (file, modified) in self.loaded_files.iter().zip(modified) {
+            *file
+                .modified
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner) = modified;
+        }
+        Ok(Some(hocon))
+    }
+
+    // Parse again the files loaded with `load_file`, keeping the other documents unchanged
+    fn read_loaded_files_again(&self) -> Result<Hocon, Error> {
+        let loaded = &self.internal.internal;
+        let mut internal = internals::HoconInternal::empty();
+        let mut start = 0;
+        for file in &self.loaded_files {
+            internal = internal
+                .add(internals::HoconInternal {
+                    internal: loaded[start..file.range.start].to_vec(),
+                })
+                .add(internals::HoconInternal::from_source(file.source.clone()))
+                .add(Self::parse_loaded_file(&file.config)?);
+            start = file.range.end;
+        }
+        internal = internal.add(internals::HoconInternal {
+            internal: loaded[start..].to_vec(),
+        });
+        Self {
+            internal,
+            ..self.clone()
+        }
+        .hocon()
+    }
+
+    /// Load the documents as HOCON, and watch the files loaded with
+    /// [`load_file`](struct.HoconLoader.html#method.load_file), as well as the files they
+    /// include, for changes. Each time one of them changes on disk, the documents are loaded
+    /// again and the result is available from the returned
+    /// [`HoconChanges`](struct.HoconChanges.html). Watching stops when it is dropped.
+    ///
+    /// ```rust,no_run
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let (doc, changes) = HoconLoader::new().load_file("app.conf")?.watch()?;
+    /// for doc in changes {
+    ///     // use the updated document
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Io`](enum.Error.html#variant.Io) if the files could not be watched
+    /// * Any error that would be returned by [`hocon()`](struct.HoconLoader.html#method.hocon)
+    #[cfg(feature = "watch")]
+    pub fn watch(self) -> Result<(Hocon, HoconChanges), Error> {
+        use notify::Watcher;
+
+        let hocon = self.clone().hocon()?;
+        let watched_files = self
+            .config
+            .read_files
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .filter_map(|path| path.canonicalize().ok())
+            .collect::<Vec<_>>();
+        let mut watched_dirs = watched_files
+            .iter()
+            .filter_map(|path| path.parent())
+            .collect::<Vec<_>>();
+        watched_dirs.sort();
+        watched_dirs.dedup();
+
+        let io_error = |err: notify::Error| Error::Io {
+            message: err.to_string(),
+        };
+        let (events_sender, events) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(events_sender).map_err(io_error)?;
+        // watch directories rather than files, as editors often replace a file when saving it
+        for dir in watched_dirs {
+            watcher
+                .watch(dir, notify::RecursiveMode::NonRecursive)
+                .map_err(io_error)?;
+        }
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        // the events channel is closed when the watcher is dropped with the `HoconChanges`
+        std::thread::spawn(move || {
+            while let Ok(event) = events.recv() {
+                let changed = match event {
+                    Ok(event) => {
+                        matches!(
+                            event.kind,
+                            notify::EventKind::Create(_) | notify::EventKind::Modify(_)
+                        ) && event.paths.iter().any(|path| watched_files.contains(path))
+                    }
+                    Err(_) => false,
+                };
+                if !changed {
+                    continue;
+                }
+                // a file is often written in several steps, wait for its changes to settle
+                while events
+                    .recv_timeout(std::time::Duration::from_millis(50))
+                    .is_ok()
+                {}
+                if sender.send(self.read_loaded_files_again()).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok((
+            hocon,
+            HoconChanges {
+                receiver,
+                _watcher: watcher,
+            },
+        ))
+    }
+
+    /// Load an `Hocon` document from a reader, such as a network stream. As when loading
+    /// from a string, includes are not supported
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let reader: Box<dyn std::io::Read> = Box::new("{ a: 7 }".as_bytes());
+    /// let doc = HoconLoader::new().load_reader(reader)?.hocon()?;
+    /// assert_eq!(doc["a"].as_i64(), Some(7));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Io`](enum.Error.html#variant.Io) if there was an error reading from the
+    ///   reader
+    /// * [`Error::Encoding`](enum.Error.html#variant.Encoding) if the document is not valid
+    ///   UTF-8
+    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
+    ///
+    /// # Additional errors in strict mode
+    ///
+    /// * [`Error::IncludeNotAllowedFromStr`](enum.Error.html#variant.IncludeNotAllowedFromStr)
+    ///   if there is an include in the document
+    pub fn load_reader<R: std::io::Read>(self, mut reader: R) -> Result<Self, Error> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        self.load_bytes(&bytes)
+    }
+
+    /// Load an `Hocon` document from bytes that should be valid UTF-8. As when loading
+    /// from a string, includes are not supported
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_bytes(b"{ a: 7 }")?.hocon()?;
+    /// assert_eq!(doc["a"].as_i64(), Some(7));
+    ///
+    /// let invalid = HoconLoader::new().load_bytes(b"{ a: \xff }");
+    /// assert_eq!(invalid.map(|_| ()), Err(Error::Encoding { offset: 5 }));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Encoding`](enum.Error.html#variant.Encoding) if the document is not valid
+    ///   UTF-8, with the offset of the first invalid byte
+    /// * [`Error::Parse`](enum.Error.html#variant.Parse) if the document is invalid
+    ///
+    /// # Additional errors in strict mode
+    ///
+    /// * [`Error::IncludeNotAllowedFromStr`](enum.Error.html#variant.IncludeNotAllowedFromStr)
+    ///   if there is an include in the document
+    pub fn load_bytes(self, bytes: &[u8]) -> Result<Self, Error> {
+        let s = std::str::from_utf8(bytes).map_err(|err| Error::Encoding {
+            offset: err.valid_up_to(),
+        })?;
+        self.load_from_str_of_conf_file(
+            FileRead {
+                hocon: Some(String::from(s)),
+                ..Default::default()
+            },
+            String::from("bytes"),
+        )
+    }
+
+    /// Load a JSON document already parsed as a `serde_json::Value`. It is merged with the
+    /// other loaded documents like any of them, so documents loaded after it take precedence
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// # #[cfg(feature = "serde-support")]
+    /// # {
+    /// let value = serde_json::json!({ "db": { "host": "localhost", "port": 5432 } });
+    /// let doc = HoconLoader::new()
+    ///     .load_json_value(&value)?
+    ///     .load_str("db.port = 6543")?
+    ///     .hocon()?;
+    /// assert_eq!(doc["db"]["host"].as_string(), Some(String::from("localhost")));
+    /// assert_eq!(doc["db"]["port"].as_i64(), Some(6543));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde-support")]
+    pub fn load_json_value(self, value: &serde_json::Value) -> Result<Self, Error> {
+        Ok(Self {
+            internal: self
+                .internal
+                .add(internals::HoconInternal::from_source(String::from(
+                    "json value",
+                )))
+                .add(internals::HoconInternal::from_json(value, &self.config)),
+            config: self.config,
+            loaded_files: self.loaded_files,
+        })
+    }
+
+    /// Load either a file or a string containing an `Hocon` document. If `input` is the
+    /// path of an existing file, it is loaded as with
+    /// [`load_file`](struct.HoconLoader.html#method.load_file), otherwise it is parsed as
+    /// with [`load_str`](struct.HoconLoader.html#method.load_str).
+    ///
+    /// This is ambiguous: a valid HOCON document that happens to match the name of a file
+    /// in the current directory (for example `a.conf`, which is also the document
+    /// `{ a: conf }`) will be loaded as a file. Use `load_file` or `load_str` when the kind
+    /// of input is known.
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let from_file = HoconLoader::new().load("tests/data/basic.conf")?.hocon()?;
+    /// assert_eq!(from_file["a"].as_i64(), Some(5));
+    ///
+    /// let from_str = HoconLoader::new().load("{ a: 7 }")?.hocon()?;
+    /// assert_eq!(from_str["a"].as_i64(), Some(7));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * Errors from [`load_file`](struct.HoconLoader.html#method.load_file) if `input` is
+    ///   an existing path
+    /// * Errors from [`load_str`](struct.HoconLoader.html#method.load_str) otherwise
+    pub fn load(self, input: &str) -> Result<Self, Error> {
+        if Path::new(input).is_file() {
+            self.load_file(input)
+        } else {
+            self.load_str(input)
+        }
+    }
+
+    /// Load the documents as HOCON
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::TooManyKeys`](enum.Error.html#variant.TooManyKeys) if the document has more
+    ///   keys and array elements than allowed by
+    ///   [`max_keys`](struct.HoconLoader.html#method.max_keys)
+    ///
+    /// # Errors in strict mode
+    ///
+    /// * [`Error::Include`](enum.Error.html#variant.Include) if there was an issue with an
+    /// included file
+    /// * [`Error::KeyNotFound`](enum.Error.html#variant.KeyNotFound) if there is a substitution
+    /// with a key that is not present in the document
+    /// * [`Error::DisabledExternalUrl`](enum.Error.html#variant.DisabledExternalUrl) if crate
+    /// was built without feature `url-support` and an `include url("...")` was found
+    pub fn hocon(self) -> Result<Hocon, Error> {
+        self.hocon_with_warnings().map(|(hocon, _)| hocon)
+    }
+
+    /// Load the documents as HOCON, like [`hocon()`](struct.HoconLoader.html#method.hocon),
+    /// but never failing: an error is returned as a
+    /// [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) document
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .strict()
+    ///     .load_str(r#"{ a: ${missing} }"#)?
+    ///     .hocon_lenient();
+    /// assert_eq!(
+    ///     doc,
+    ///     Hocon::BadValue(Error::KeyNotFound { key: String::from("missing") })
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn hocon_lenient(self) -> Hocon {
+        self.hocon().unwrap_or_else(Hocon::BadValue)
+    }
+
+    /// Load the documents as HOCON, resolving as many substitutions as possible. Substitutions
+    /// that can't be resolved yet are left as a
+    /// [`Error::KeyNotFound`](enum.Error.html#variant.KeyNotFound) bad value, and their paths
+    /// are returned so that the missing values can be provided before resolving again.
+    ///
+    /// A concatenation containing a pending substitution is pending as a whole.
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let loader = HoconLoader::new()
+    ///     .no_system()
+    ///     .load_str(r#"{ a: 1, b: ${a}, c: ${missing} }"#)?;
+    ///
+    /// let (doc, pending) = loader.clone().pending_substitutions();
+    /// assert_eq!(doc["b"], Hocon::Integer(1));
+    /// assert_eq!(pending, vec![String::from("missing")]);
+    ///
+    /// let doc = loader.load_str("missing = 2")?.hocon()?;
+    /// assert_eq!(doc["c"], Hocon::Integer(2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pending_substitutions(self) -> (Hocon, Vec<String>) {
+        fn collect_pending(hocon: &Hocon, pending: &mut Vec<String>) {
+            match hocon {
+                Hocon::BadValue(Error::KeyNotFound { key }) if !pending.contains(key) => {
+                    pending.push(key.clone())
+                }
+                Hocon::Array(values) => values
+                    .iter()
+                    .for_each(|value| collect_pending(value, pending)),
+                Hocon::Hash(values) => values
+                    .values()
+                    .for_each(|value| collect_pending(value, pending)),
+                _ => (),
+            }
+        }
+
+        let loader = Self {
+            config: HoconLoaderConfig {
+                strict: false,
+                partial_substitutions: true,
+                ..self.config.clone()
+            },
+            ..self
+        };
+        let hocon = loader.hocon_lenient();
+        let mut pending = vec![];
+        collect_pending(&hocon, &mut pending);
+        (hocon, pending)
+    }
+
+    /// Load the documents as HOCON, also returning warnings about the documents. Warnings
+    /// are currently emitted for keys marked as deprecated with
+    /// [`deprecate_keys`](struct.HoconLoader.html#method.deprecate_keys).
+    ///
+    /// # Errors in strict mode
+    ///
+    /// * Any error that would be returned by [`hocon()`](struct.HoconLoader.html#method.hocon)
+    pub fn hocon_with_warnings(self) -> Result<(Hocon, Vec<String>), Error> {
+        let config = &self.config;
+        let hocon = self.internal.merge(config)?.finalize(config)?;
+        let hocon = match (&config.root_key, hocon) {
+            (Some(key), hocon @ Hocon::Array(_))
+            | (Some(key), hocon @ Hocon::Real(_))
+            | (Some(key), hocon @ Hocon::Integer(_))
+            | (Some(key), hocon @ Hocon::String(_))
+            | (Some(key), hocon @ Hocon::Boolean(_))
+            | (Some(key), hocon @ Hocon::Null) => {
+                Hocon::Hash(std::iter::once((key.clone(), hocon)).collect())
+            }
+            (_, hocon) => hocon,
+        };
+        let mut hocon = match (&config.profile, hocon) {
+            (Some(profile), Hocon::Hash(mut root)) => {
+                let selected = match root.remove("profiles") {
+                    Some(Hocon::Hash(mut profiles)) => profiles.remove(profile),
+                    _ => None,
+                };
+                match selected {
+                    Some(selected) => Hocon::Hash(root).merge(selected),
+                    None => Hocon::Hash(root),
+                }
+            }
+            (_, hocon) => hocon,
+        };
+
+        let mut warnings = vec![];
+        for (deprecated, replacement) in &config.deprecated_keys {
+            let value = match hocon.at_path(deprecated) {
+                Hocon::BadValue(_) => continue,
+                value => value.clone(),
+            };
+            warnings.push(format!(
+                "key \"{}\" is deprecated, use \"{}\" instead",
+                deprecated, replacement
+            ));
+            if let Hocon::BadValue(_) = hocon.at_path(replacement) {
+                hocon.set_dotted(replacement, value);
+            }
+        }
+
+        Ok((hocon, warnings))
+    }
+
+    /// Get the source of each value in the loaded documents, as a map from the full path
+    /// of the value (with keys separated by `.`) to the document that provided it. When a
+    /// key is defined several times, the source is the document whose value was kept after
+    /// merging.
+    ///
+    /// The source is the path of the file for documents loaded with
+    /// [`load_file`](struct.HoconLoader.html#method.load_file), `"string"` for documents
+    /// loaded with [`load_str`](struct.HoconLoader.html#method.load_str), and the
+    /// included file or url for values coming from an `include`.
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let provenance = HoconLoader::new()
+    ///     .load_str("{ a: 1, z: 2 }")?
+    ///     .load_file("tests/data/basic.conf")?
+    ///     .provenance()?;
+    ///
+    /// assert_eq!(provenance["a"], "tests/data/basic.conf");
+    /// assert_eq!(provenance["z"], "string");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors in strict mode
+    ///
+    /// * Any error that would be returned by [`hocon()`](struct.HoconLoader.html#method.hocon)
+    pub fn provenance(self) -> Result<HashMap<String, String>, Error> {
+        let mut provenance = HashMap::new();
+        self.internal
+            .merge_with_provenance(&self.config, Some(&mut provenance), None)?;
+        Ok(provenance)
+    }
+
+    /// Get the text of the real numbers of the loaded documents, as written, for documents
+    /// loaded with [`preserve_number_text`](struct.HoconLoader.html#method.preserve_number_text).
+    /// This is a map from the full path of the value (with keys separated by `.`) to its
+    /// text, that can be used to render the document with
+    /// [`to_hocon_string_with_number_texts`](enum.Hocon.html#method.to_hocon_string_with_number_texts).
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let number_texts = HoconLoader::new()
+    ///     .preserve_number_text()
+    ///     .load_str("{ a: 1.0, b: 2, c: { d: 1e3 } }")?
+    ///     .number_texts()?;
+    ///
+    /// assert_eq!(number_texts["a"], "1.0");
+    /// assert_eq!(number_texts["c.d"], "1e3");
+    /// assert!(!number_texts.contains_key("b"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors in strict mode
+    ///
+    /// * Any error that would be returned by [`hocon()`](struct.HoconLoader.html#method.hocon)
+    pub fn number_texts(self) -> Result<HashMap<String, String>, Error> {
+        let mut number_texts = HashMap::new();
+        self.internal
+            .merge_with_provenance(&self.config, None, Some(&mut number_texts))?;
+        Ok(number_texts)
+    }
+
+    /// Validate the loaded documents against a schema. The schema is an HOCON document
+    /// with the same structure, and with the expected type as a string for each key:
+    /// `"integer"`, `"real"`, `"string"`, `"boolean"`, `"array"`, `"hash"` or `"null"`.
+    /// An array with a single element in the schema describes the type of each element.
+    ///
+    /// Types are checked with the same
+    /// [automatic type conversions](https://github.com/lightbend/config/blob/master/HOCON.md#automatic-type-conversions)
+    /// as the `as_*` methods on [`Hocon`](enum.Hocon.html), so `"80"` is a valid `integer`.
+    /// Keys not present in the schema are ignored.
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let schema = HoconLoader::new()
+    ///     .load_str(r#"{ port: "integer", host: "string" }"#)?
+    ///     .hocon()?;
+    ///
+    /// HoconLoader::new()
+    ///     .load_str(r#"{ port: 80, host: localhost }"#)?
+    ///     .validate_against(&schema)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Deserialization`](enum.Error.html#variant.Deserialization) listing all
+    ///   values not matching the schema
+    ///
+    /// # Additional errors in strict mode
+    ///
+    /// * Any error that would be returned by [`hocon()`](struct.HoconLoader.html#method.hocon)
+    pub fn validate_against(&self, schema: &Hocon) -> Result<(), Error> {
+        let mut mismatches = vec![];
+        self.clone()
+            .hocon()?
+            .collect_schema_mismatches(schema, "", &mut mismatches);
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Deserialization {
+                message: mismatches.join(", "),
+            })
+        }
+    }
+
+    /// Deserialize the loaded documents to the target type
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Deserialization`](enum.Error.html#variant.Deserialization) if there was a
+    /// serde error during deserialization (missing required field, type issue, ...)
+    ///
+    /// # Additional errors in strict mode
     ///
     /// * [`Error::Include`](enum.Error.html#variant.Include) if there was an issue with an
     /// included file
@@ -504,7 +1723,13 @@ impl HoconLoader {
     where
         T: ::serde::Deserialize<'de>,
     {
-        self.hocon()?.resolve()
+        if self.config.strict_types {
+            de::from_hocon_with_strict_types(self.hocon()?).map_err(|err| Error::Deserialization {
+                message: err.message,
+            })
+        } else {
+            self.hocon()?.resolve()
+        }
     }
 }
 
@@ -532,6 +1757,34 @@ mod tests {
         assert_eq!(doc["a"]["b"].as_string(), Some(String::from("c")));
     }
 
+    #[test]
+    fn load_str_with_file_type() {
+        let s = "a.b = value # not a comment";
+        let doc = HoconLoader::new()
+            .load_str_as(s, super::FileType::Properties)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(
+            doc["a"]["b"].as_string(),
+            Some(String::from("value # not a comment"))
+        );
+
+        let doc = HoconLoader::new()
+            .load_str_as(s, super::FileType::Hocon)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["a"]["b"].as_string(), Some(String::from("value")));
+
+        let doc = HoconLoader::new()
+            .load_str_as(r#"{"a": {"b": [1, 2]}}"#, super::FileType::Json)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["a"]["b"][1], Hocon::Integer(2));
+    }
+
     #[test]
     fn read_from_hocon() {
         let s = r#"a.b:c"#;
@@ -650,11 +1903,371 @@ mod tests {
         assert_eq!(
             res.unwrap_err(),
             super::Error::Deserialization {
-                message: String::from("int: Invalid type for field \"int\", expected integer")
+                message: String::from(
+                    "int: Invalid type for field \"int\", expected integer, found string"
+                )
             }
         );
     }
 
+    #[test]
+    fn load_detects_file_or_string() {
+        let from_file = HoconLoader::new()
+            .load("tests/data/basic.conf")
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(from_file["a"].as_i64(), Some(5));
+
+        let from_str = HoconLoader::new()
+            .load("{a:1}")
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(from_str["a"].as_i64(), Some(1));
+    }
+
+    #[test]
+    fn load_from_reader() {
+        let doc = HoconLoader::new()
+            .load_reader(std::io::Cursor::new("{ a: 7 }"))
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["a"], Hocon::Integer(7));
+
+        let doc = HoconLoader::new()
+            .strict()
+            .load_reader(std::io::Cursor::new(r#"{ include "basic.conf" }"#));
+        assert_eq!(doc.map(|_| ()), Err(super::Error::IncludeNotAllowedFromStr));
+    }
+
+    #[test]
+    fn lenient_hocon_on_broken_document() {
+        let doc = HoconLoader::new()
+            .strict()
+            .no_system()
+            .load_str(r#"{ a: 1, b: ${missing} }"#)
+            .expect("during test")
+            .hocon_lenient();
+        assert_eq!(
+            doc,
+            Hocon::BadValue(super::Error::KeyNotFound {
+                key: String::from("missing")
+            })
+        );
+
+        let doc = HoconLoader::new()
+            .load_str(r#"{ a: 1 }"#)
+            .expect("during test")
+            .hocon_lenient();
+        assert_eq!(doc["a"], Hocon::Integer(1));
+    }
+
+    #[test]
+    fn limit_number_of_keys() {
+        let s = r#"{ a: { b: 1, c: [1, 2] } }"#;
+        let doc = HoconLoader::new()
+            .max_keys(5)
+            .load_str(s)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["a"]["c"][1], Hocon::Integer(2));
+
+        let doc = HoconLoader::new()
+            .max_keys(4)
+            .load_str(s)
+            .expect("during test")
+            .hocon();
+        assert_eq!(doc, Err(super::Error::TooManyKeys));
+    }
+
+    #[test]
+    fn limit_number_of_keys_while_merging() {
+        // the object is merged before being replaced by `a: 1`
+        let doc = HoconLoader::new()
+            .max_keys(3)
+            .load_str(r#"{ a: { b: 1, c: 2, d: 3 }, a: 1 }"#)
+            .expect("during test")
+            .hocon();
+        assert_eq!(doc, Err(super::Error::TooManyKeys));
+
+        // the substitution copies the object once resolved
+        let s = r#"{ a: { b: 1, c: 2 }, d: ${a} }"#;
+        let doc = HoconLoader::new()
+            .max_keys(6)
+            .load_str(s)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["d"]["c"], Hocon::Integer(2));
+
+        let doc = HoconLoader::new()
+            .max_keys(5)
+            .load_str(s)
+            .expect("during test")
+            .hocon();
+        assert_eq!(doc, Err(super::Error::TooManyKeys));
+    }
+
+    #[test]
+    fn env_prefix_scopes_system_substitution() {
+        std::env::set_var("HOCON_TEST_PREFIX_FOO", "prefixed");
+        std::env::set_var("HOCON_TEST_UNPREFIXED", "unprefixed");
+
+        let doc = HoconLoader::new()
+            .with_env_prefix("HOCON_TEST_PREFIX_")
+            .load_str(r#"{ a: ${FOO}, b: ${HOCON_TEST_UNPREFIXED} }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["a"], Hocon::String(String::from("prefixed")));
+        assert_eq!(
+            doc["b"],
+            Hocon::BadValue(super::Error::KeyNotFound {
+                key: String::from("HOCON_TEST_UNPREFIXED")
+            })
+        );
+    }
+
+    #[test]
+    fn env_separator_maps_substitution_path() {
+        std::env::set_var("APP__db__max_conn", "20");
+        std::env::set_var("APP__db.max_conn", "wrong");
+
+        let doc = HoconLoader::new()
+            .with_env_prefix("APP__")
+            .env_separator("__")
+            .load_str(r#"{ max: ${db.max_conn} }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["max"], Hocon::String(String::from("20")));
+
+        let doc = HoconLoader::new()
+            .with_env_prefix("APP__")
+            .load_str(r#"{ max: ${db.max_conn} }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["max"], Hocon::String(String::from("wrong")));
+    }
+
+    #[test]
+    fn substitution_from_source() {
+        std::env::set_var("HOCON_TEST_SOURCE_ENV", "from env");
+        let source: std::collections::HashMap<String, String> = vec![
+            (String::from("HOME"), String::from("/home/test")),
+            (String::from("app.name"), String::from("test")),
+        ]
+        .into_iter()
+        .collect();
+
+        let doc = HoconLoader::new()
+            .no_system()
+            .with_substitution_source(source.clone())
+            .load_str(r#"{ home: ${HOME}, name: ${app.name}, env: ${HOCON_TEST_SOURCE_ENV} }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["home"], Hocon::String(String::from("/home/test")));
+        assert_eq!(doc["name"], Hocon::String(String::from("test")));
+        assert_eq!(
+            doc["env"],
+            Hocon::BadValue(super::Error::KeyNotFound {
+                key: String::from("HOCON_TEST_SOURCE_ENV")
+            })
+        );
+
+        let doc = HoconLoader::new()
+            .with_substitution_source(source)
+            .load_str(r#"{ home: ${HOME}, env: ${HOCON_TEST_SOURCE_ENV} }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["home"], Hocon::String(String::from("/home/test")));
+        assert_eq!(doc["env"], Hocon::String(String::from("from env")));
+    }
+
+    #[test]
+    fn load_from_bytes() {
+        let doc = HoconLoader::new()
+            .load_bytes("{ a: \"é\" }".as_bytes())
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["a"], Hocon::String(String::from("é")));
+
+        let doc = HoconLoader::new().load_bytes(b"{ a: 1\n  b: \xc3\x28 }");
+        assert_eq!(doc.map(|_| ()), Err(super::Error::Encoding { offset: 12 }));
+
+        let doc = HoconLoader::new().load_reader(std::io::Cursor::new(b"\xff"));
+        assert_eq!(doc.map(|_| ()), Err(super::Error::Encoding { offset: 0 }));
+    }
+
+    #[test]
+    fn deprecated_keys_are_mirrored() {
+        let mut mapping = std::collections::HashMap::new();
+        mapping.insert(String::from("old.port"), String::from("port"));
+        mapping.insert(String::from("timeout"), String::from("request_timeout"));
+        mapping.insert(String::from("absent"), String::from("present"));
+
+        let (doc, warnings) = HoconLoader::new()
+            .deprecate_keys(mapping)
+            .load_str("{ old.port = 80, timeout = 5, request_timeout = 10 }")
+            .expect("during test")
+            .hocon_with_warnings()
+            .expect("during test");
+
+        assert_eq!(doc["port"], Hocon::Integer(80));
+        assert_eq!(doc["old"]["port"], Hocon::Integer(80));
+        assert_eq!(doc["request_timeout"], Hocon::Integer(10));
+        assert_eq!(doc["present"], Hocon::BadValue(crate::Error::MissingKey));
+        assert_eq!(
+            warnings,
+            vec![
+                String::from("key \"old.port\" is deprecated, use \"port\" instead"),
+                String::from("key \"timeout\" is deprecated, use \"request_timeout\" instead"),
+            ]
+        );
+    }
+
+    #[test]
+    fn profile_overrides_defaults() {
+        let doc = HoconLoader::new()
+            .with_profile("prod")
+            .load_str(
+                r#"{
+                    db { host: localhost, port: 5432 }
+                    debug: true
+                    profiles {
+                        dev { debug_port: 5005 }
+                        prod { db.host: db.example.com, debug: false }
+                    }
+                }"#,
+            )
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(
+            doc["db"]["host"],
+            Hocon::String(String::from("db.example.com"))
+        );
+        assert_eq!(doc["db"]["port"], Hocon::Integer(5432));
+        assert_eq!(doc["debug"], Hocon::Boolean(false));
+        assert_eq!(doc["debug_port"], Hocon::BadValue(crate::Error::MissingKey));
+        assert_eq!(doc["profiles"], Hocon::BadValue(crate::Error::MissingKey));
+    }
+
+    #[test]
+    fn intern_strings_keep_the_same_document() {
+        let s = r#"{
+            a = blue, b = "blue", c = ${a}, d = [blue, red, ${e}], e = red
+            f = bl"ue", g.a = blue, h = [{ a = blue }, { a = red }]
+        }"#;
+        let doc = HoconLoader::new()
+            .intern_strings()
+            .load_str(s)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        let not_interned = HoconLoader::new()
+            .load_str(s)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc, not_interned);
+        assert_eq!(doc["f"], Hocon::String(String::from("blue")));
+        assert_eq!(doc["h"][1]["a"], Hocon::String(String::from("red")));
+    }
+
+    #[test]
+    fn root_key_for_array_document() {
+        let doc = HoconLoader::new()
+            .root_key("items")
+            .load_str("[1, 2, 3]")
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["items"][0], Hocon::Integer(1));
+        assert_eq!(doc["items"][2], Hocon::Integer(3));
+
+        let doc = HoconLoader::new()
+            .root_key("items")
+            .load_str("{ a: 1 }")
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["a"], Hocon::Integer(1));
+        assert_eq!(doc["items"], Hocon::BadValue(crate::Error::MissingKey));
+    }
+
+    #[test]
+    fn provenance_of_overridden_keys() {
+        let provenance = HoconLoader::new()
+            .load_str("{ a: 1, z: 2, f: 3 }")
+            .expect("during test")
+            .load_file("tests/data/include_file.conf")
+            .expect("during test")
+            .provenance()
+            .expect("during test");
+
+        assert_eq!(provenance["a"], "tests/data/include_file.conf");
+        assert_eq!(provenance["z"], "string");
+        assert_eq!(provenance["b"], "basic.conf");
+        assert_eq!(provenance["f.g"], "basic.conf");
+        assert_eq!(provenance["f.h"], "tests/data/include_file.conf");
+        assert!(!provenance.contains_key("f"));
+    }
+
+    #[test]
+    fn validate_against_schema() {
+        let schema = HoconLoader::new()
+            .load_str(r#"{ port: "integer", host: "string", db { tags: ["string"] } }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        let conforming = HoconLoader::new()
+            .load_str(r#"{ port: 80, host: localhost, db { tags: [a, b] }, extra: true }"#)
+            .expect("during test");
+        assert_eq!(conforming.validate_against(&schema), Ok(()));
+
+        let non_conforming = HoconLoader::new()
+            .load_str(r#"{ port: eighty, db { tags: [a, [b]] } }"#)
+            .expect("during test");
+        assert_eq!(
+            non_conforming.validate_against(&schema),
+            Err(super::Error::Deserialization {
+                message: String::from(
+                    "port: expected integer, host: missing value, db.tags.1: expected string"
+                )
+            })
+        );
+    }
+
+    #[test]
+    fn validate_root_array_against_schema() {
+        let schema = HoconLoader::new()
+            .load_str(r#"[{ name: "string", port: "integer" }]"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        let doc = HoconLoader::new()
+            .load_str(r#"[{ name: a, port: 80 }, { port: eighty }, 3]"#)
+            .expect("during test");
+        assert_eq!(
+            doc.validate_against(&schema),
+            Err(super::Error::Deserialization {
+                message: String::from(
+                    "1.name: missing value, 1.port: expected integer, 2: expected hash"
+                )
+            })
+        );
+    }
+
     #[cfg(feature = "url-support")]
     #[test]
     fn can_disable_url_include() {
@@ -669,7 +2282,8 @@ mod tests {
             doc["https://raw.githubusercontent.com/mockersf/hocon.rs/master/tests/data/basic.conf"],
             Hocon::BadValue(
                 super::Error::Include {
-                    path: String::from("https://raw.githubusercontent.com/mockersf/hocon.rs/master/tests/data/basic.conf")
+                    path: String::from("https://raw.githubusercontent.com/mockersf/hocon.rs/master/tests/data/basic.conf"),
+                    chain: vec![String::from("tests/data/include_url.conf")],
                 }
             )
         );
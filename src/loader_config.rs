@@ -1,15 +1,23 @@
 use crate::Error;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fs::File;
 use std::io::prelude::*;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
 
-#[derive(Debug, Clone)]
-pub(crate) enum FileType {
+/// Format of a document
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileType {
+    /// A Java properties document
     Properties,
+    /// An HOCON document
     Hocon,
+    /// A JSON document
     Json,
-    All,
 }
 
 #[derive(Default, Debug)]
@@ -19,7 +27,7 @@ pub(crate) struct FileRead {
     pub(crate) hocon: Option<String>,
 }
 impl FileRead {
-    fn from_file_type(ft: &FileType, s: String) -> Self {
+    pub(crate) fn from_file_type(ft: &FileType, s: String) -> Self {
         match ft {
             FileType::Properties => Self {
                 properties: Some(s),
@@ -33,7 +41,6 @@ impl FileRead {
                 hocon: Some(s),
                 ..Default::default()
             },
-            FileType::All => unimplemented!(),
         }
     }
 }
@@ -42,7 +49,8 @@ impl FileRead {
 pub(crate) struct ConfFileMeta {
     path: PathBuf,
     full_path: PathBuf,
-    file_type: FileType,
+    // `None` when the format is not known from the extension, to try all formats
+    file_type: Option<FileType>,
 }
 impl ConfFileMeta {
     pub(crate) fn from_path(path: PathBuf) -> Self {
@@ -58,48 +66,240 @@ impl ConfFileMeta {
             path: parent_path,
             full_path: path.clone(),
             file_type: match Path::new(file).extension().and_then(OsStr::to_str) {
-                Some("properties") => FileType::Properties,
-                Some("json") => FileType::Json,
-                Some("conf") => FileType::Hocon,
-                _ => FileType::All,
+                Some("properties") => Some(FileType::Properties),
+                Some("json") => Some(FileType::Json),
+                Some("conf") => Some(FileType::Hocon),
+                _ => None,
             },
         }
     }
+
+    // Last modification time of the file, or of the most recent of the files with this base
+    // name when the format is not known
+    fn modified(&self) -> Option<SystemTime> {
+        let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        match self.file_type {
+            Some(_) => modified(&self.full_path),
+            None => ["conf", "json", "properties"]
+                .iter()
+                .map(|extension| self.full_path.with_extension(extension))
+                .chain(std::iter::once(self.full_path.clone()))
+                .filter_map(|path| modified(&path))
+                .max(),
+        }
+    }
+}
+
+// A document loaded from a file, kept to read it again when reloading
+#[derive(Debug)]
+pub(crate) struct LoadedFile {
+    pub(crate) config: HoconLoaderConfig,
+    pub(crate) source: String,
+    // where the document is in the loaded documents
+    pub(crate) range: Range<usize>,
+    pub(crate) modified: Mutex<Option<SystemTime>>,
+}
+
+impl Clone for LoadedFile {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            source: self.source.clone(),
+            range: self.range.clone(),
+            modified: Mutex::new(*self.modified.lock().unwrap_or_else(PoisonError::into_inner)),
+        }
+    }
+}
+
+/// How values are merged when a key is defined several times, either in the same document
+/// or in several loaded documents
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MergeStrategy {
+    /// Objects are merged together, other values replace the previous one, as described in
+    /// the [HOCON Specifications](https://github.com/lightbend/config/blob/master/HOCON.md#duplicate-keys-and-object-merging)
+    #[default]
+    ObjectMerge,
+    /// Objects replace the previous value instead of being merged with it
+    ReplaceAll,
+    /// Objects are merged together, and arrays are concatenated to the previous array instead
+    /// of replacing it
+    ConcatArrays,
+}
+
+type ClasspathResolverFn = dyn Fn(&str) -> Option<PathBuf> + Send + Sync;
+
+// Find the file for a classpath include
+#[derive(Clone)]
+pub(crate) struct ClasspathResolver(pub(crate) Arc<ClasspathResolverFn>);
+
+impl std::fmt::Debug for ClasspathResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("ClasspathResolver")
+    }
+}
+
+// Files read to parse a document, with their modification time when read or `None` if they
+// could not be read
+type FileReads = Vec<(PathBuf, Option<SystemTime>)>;
+
+// Document parsed from a file, with the files it was read from including its includes
+#[derive(Debug)]
+struct CachedFile {
+    reads: FileReads,
+    internal: crate::internals::HoconInternal,
+}
+
+impl CachedFile {
+    fn is_fresh(&self) -> bool {
+        self.reads
+            .iter()
+            .all(|(path, modified)| HoconLoaderConfig::modified(path) == *modified)
+    }
+}
+
+/// Cache of the documents parsed from the files loaded by a
+/// [`HoconLoader`](struct.HoconLoader.html), directly or through an `include`, that can be
+/// shared between loaders with [`with_shared_cache`](struct.HoconLoader.html#method.with_shared_cache).
+/// Files are keyed by their canonical path and encoding, and are read and parsed again when
+/// the modification time of the file or of one of its includes changes. Loaders sharing a
+/// cache should be built with the same options
+#[derive(Debug, Default)]
+pub struct FileCache {
+    files: Mutex<HashMap<(PathBuf, Option<String>), CachedFile>>,
+}
+
+impl FileCache {
+    /// New empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of files in the cache
+    pub fn len(&self) -> usize {
+        self.files
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct HoconLoaderConfig {
     pub(crate) include_depth: u8,
+    // the current file, then the files through which it was included
+    pub(crate) include_chain: Vec<String>,
     pub(crate) file_meta: Option<ConfFileMeta>,
     pub(crate) system: bool,
+    pub(crate) env_prefix: Option<String>,
+    pub(crate) env_separator: String,
+    pub(crate) substitution_source: Option<HashMap<String, String>>,
     #[cfg(feature = "url-support")]
     pub(crate) external_url: bool,
+    pub(crate) file_include: bool,
     pub(crate) strict: bool,
+    pub(crate) strict_include: bool,
+    // keep unresolved substitutions as pending instead of resolving around them
+    pub(crate) partial_substitutions: bool,
     pub(crate) max_include_depth: u8,
+    pub(crate) max_substitution_depth: u8,
+    pub(crate) max_keys: Option<usize>,
+    pub(crate) preserve_number_text: bool,
+    pub(crate) intern_strings: bool,
+    pub(crate) root_key: Option<String>,
+    pub(crate) deprecated_keys: Vec<(String, String)>,
+    pub(crate) profile: Option<String>,
+    pub(crate) merge_strategy: MergeStrategy,
+    pub(crate) unquoted_string_stop: Vec<&'static str>,
+    pub(crate) allow_space_separator: bool,
+    pub(crate) classpath_resolver: Option<ClasspathResolver>,
+    pub(crate) file_cache: Option<Arc<FileCache>>,
+    // files read while parsing a document to put in `file_cache`, shared with the configs of
+    // its includes
+    pub(crate) cached_reads: Option<Arc<Mutex<FileReads>>>,
+    pub(crate) preferred_file_type: FileType,
+    // shared between clones, so that ids are unique among all documents of a loader
+    pub(crate) item_ids: Arc<AtomicUsize>,
+    // files read while loading documents, including through includes, to be watched for
+    // changes. Shared between clones like `item_ids`
+    #[cfg(feature = "watch")]
+    pub(crate) read_files: Arc<Mutex<Vec<PathBuf>>>,
+    #[cfg(feature = "serde-support")]
+    pub(crate) strict_types: bool,
 }
 
 impl Default for HoconLoaderConfig {
     fn default() -> Self {
         Self {
             include_depth: 0,
+            include_chain: vec![],
             file_meta: None,
             system: true,
+            env_prefix: None,
+            env_separator: String::from("."),
+            substitution_source: None,
             #[cfg(feature = "url-support")]
             external_url: true,
+            file_include: true,
             strict: false,
+            strict_include: false,
+            partial_substitutions: false,
             max_include_depth: 10,
+            max_substitution_depth: 32,
+            max_keys: None,
+            preserve_number_text: false,
+            intern_strings: false,
+            root_key: None,
+            deprecated_keys: vec![],
+            profile: None,
+            merge_strategy: MergeStrategy::default(),
+            unquoted_string_stop: crate::parser::UNQUOTED_STRING_STOP.to_vec(),
+            allow_space_separator: false,
+            classpath_resolver: None,
+            file_cache: None,
+            cached_reads: None,
+            preferred_file_type: FileType::Hocon,
+            item_ids: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "watch")]
+            read_files: Arc::new(Mutex::new(vec![])),
+            #[cfg(feature = "serde-support")]
+            strict_types: false,
         }
     }
 }
 
 impl HoconLoaderConfig {
-    pub(crate) fn included_from(&self) -> Self {
+    pub(crate) fn included_from(&self, included: &str) -> Self {
         Self {
             include_depth: self.include_depth + 1,
+            include_chain: std::iter::once(String::from(included))
+                .chain(self.include_chain.iter().cloned())
+                .collect(),
             ..self.clone()
         }
     }
 
+    // Deterministic id to keep apart values that are only merged later, like the items
+    // of different `+=`
+    pub(crate) fn next_item_id(&self) -> String {
+        self.item_ids.fetch_add(1, Ordering::Relaxed).to_string()
+    }
+
+    pub(crate) fn include_error(&self, path: &str) -> Error {
+        Error::Include {
+            path: String::from(path),
+            chain: self.include_chain.clone(),
+        }
+    }
+
+    pub(crate) fn file_modified(&self) -> Option<SystemTime> {
+        self.file_meta.as_ref().and_then(ConfFileMeta::modified)
+    }
+
     pub(crate) fn with_file(&self, path: PathBuf) -> Self {
         match self.file_meta.as_ref() {
             Some(file_meta) => Self {
@@ -113,57 +313,91 @@ impl HoconLoaderConfig {
         }
     }
 
+    // When a file is read without an extension, each format found is loaded in order
+    // properties, JSON then HOCON, later formats overriding the previous ones. The preferred
+    // file type is moved last so that it wins
     pub(crate) fn parse_str_to_internal(
         &self,
         s: FileRead,
     ) -> Result<crate::internals::HoconInternal, Error> {
+        let mut file_types = vec![FileType::Properties, FileType::Json, FileType::Hocon];
+        file_types.retain(|file_type| *file_type != self.preferred_file_type);
+        file_types.push(self.preferred_file_type);
+
+        let FileRead {
+            properties,
+            json,
+            hocon,
+        } = s;
         let mut internal = crate::internals::HoconInternal::empty();
-        if let Some(properties) = s.properties {
-            internal = internal.add(
-                java_properties::read(properties.as_bytes())
-                    .map(crate::internals::HoconInternal::from_properties)
-                    .map_err(|_| crate::Error::Parse)?,
-            );
-        };
-        if let Some(json) = s.json {
-            internal = internal.add(
-                crate::parser::root(format!("{}\n\0", json.replace('\r', "\n")).as_bytes(), self)
-                    .map_err(|_| crate::Error::Parse)
-                    .and_then(|(remaining, parsed)| {
-                        if Self::remaining_only_whitespace(remaining) {
-                            parsed
-                        } else if self.strict {
-                            Err(crate::Error::Deserialization {
-                                message: String::from("file could not be parsed completely"),
-                            })
-                        } else {
-                            parsed
-                        }
-                    })?,
-            );
-        };
-        if let Some(hocon) = s.hocon {
-            internal = internal.add(
-                crate::parser::root(
-                    format!("{}\n\0", hocon.replace('\r', "\n")).as_bytes(),
-                    self,
+        for file_type in file_types {
+            let parsed = match file_type {
+                FileType::Properties => properties.as_deref().map(Self::parse_properties_str),
+                FileType::Json => json.as_deref().map(|json| self.parse_hocon_str(json)),
+                FileType::Hocon => hocon.as_deref().map(|hocon| self.parse_hocon_str(hocon)),
+            };
+            if let Some(parsed) = parsed {
+                internal = internal.add(parsed?);
+            }
+        }
+
+        Ok(internal)
+    }
+
+    fn parse_properties_str(properties: &str) -> Result<crate::internals::HoconInternal, Error> {
+        java_properties::read(properties.as_bytes())
+            .map(crate::internals::HoconInternal::from_properties)
+            .map_err(|err| {
+                let line = err.line_number().unwrap_or(1);
+                Self::parse_error(
+                    properties,
+                    properties
+                        .split('\n')
+                        .take(line - 1)
+                        .map(|line| line.len() + 1)
+                        .sum(),
                 )
-                .map_err(|_| crate::Error::Parse)
-                .and_then(|(remaining, parsed)| {
-                    if Self::remaining_only_whitespace(remaining) {
-                        parsed
-                    } else if self.strict {
-                        Err(crate::Error::Deserialization {
-                            message: String::from("file could not be parsed completely"),
-                        })
-                    } else {
-                        parsed
-                    }
-                })?,
-            );
+            })
+    }
+
+    fn parse_hocon_str(&self, s: &str) -> Result<crate::internals::HoconInternal, Error> {
+        let input = format!("{}\n\0", s.replace('\r', "\n"));
+        let remaining = match crate::parser::root(input.as_bytes(), self) {
+            Ok((remaining, parsed)) => {
+                if Self::remaining_only_whitespace(remaining) || !self.strict {
+                    return parsed;
+                }
+                remaining
+            }
+            Err(nom::Err::Error(nom::Context::Code(remaining, _)))
+            | Err(nom::Err::Failure(nom::Context::Code(remaining, _))) => remaining,
+            Err(nom::Err::Incomplete(_)) => &[],
         };
+        Err(Self::parse_error(s, input.len() - remaining.len()))
+    }
 
-        Ok(internal)
+    // Build a parse error pointing at the first non whitespace character from the byte
+    // `offset` in the document `s`
+    fn parse_error(s: &str, offset: usize) -> Error {
+        let mut offset = offset.min(s.len());
+        while !s.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        if !s[offset..].trim().is_empty() {
+            offset = s.len() - s[offset..].trim_start().len();
+        }
+        let line_start = s[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Error::Parse {
+            line: s[..offset].matches('\n').count() + 1,
+            column: s[line_start..offset].chars().count() + 1,
+            snippet: s[offset..]
+                .lines()
+                .next()
+                .unwrap_or("")
+                .chars()
+                .take(20)
+                .collect(),
+        }
     }
 
     fn remaining_only_whitespace(remaining: &[u8]) -> bool {
@@ -178,45 +412,181 @@ impl HoconLoaderConfig {
             .unwrap_or(true)
     }
 
-    pub(crate) fn read_file_to_string(path: PathBuf) -> Result<String, Error> {
+    // Parse the file of `file_meta` with `parse`, or reuse the document parsed from it by a
+    // previous load if it's in the shared cache and none of the files it was read from changed
+    pub(crate) fn parse_file_cached(
+        &self,
+        encoding: Option<&str>,
+        parse: impl FnOnce(&Self) -> Result<crate::internals::HoconInternal, Error>,
+    ) -> Result<crate::internals::HoconInternal, Error> {
+        let cache = match self.file_cache.as_ref() {
+            Some(cache) => cache,
+            None => return parse(self),
+        };
+        let path = self
+            .file_meta
+            .as_ref()
+            .expect("missing file metadata")
+            .full_path
+            .clone();
+        let key = (
+            path.canonicalize().unwrap_or(path),
+            encoding.map(String::from),
+        );
+        if let Some(cached) = cache
+            .files
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+            .filter(|cached| cached.is_fresh())
+        {
+            self.record_reads(&cached.reads);
+            return Ok(cached.internal.clone().with_new_item_ids(self));
+        }
+
+        let cached_reads = Arc::new(Mutex::new(vec![]));
+        let internal = parse(&Self {
+            cached_reads: Some(cached_reads.clone()),
+            ..self.clone()
+        })?;
+        let reads =
+            std::mem::take(&mut *cached_reads.lock().unwrap_or_else(PoisonError::into_inner));
+        self.record_reads(&reads);
+        cache
+            .files
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                key,
+                CachedFile {
+                    reads,
+                    internal: internal.clone(),
+                },
+            );
+        Ok(internal)
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    // Keep track of files read, for the document being cached and for watching changes
+    fn record_reads(&self, reads: &[(PathBuf, Option<SystemTime>)]) {
+        if let Some(cached_reads) = self.cached_reads.as_ref() {
+            cached_reads
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .extend(reads.iter().cloned());
+        }
+        #[cfg(feature = "watch")]
+        {
+            let mut read_files = self
+                .read_files
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            for (path, _) in reads {
+                if !read_files.contains(path) {
+                    read_files.push(path.clone());
+                }
+            }
+        }
+    }
+
+    fn read_file_to_string(&self, path: PathBuf, encoding: Option<&str>) -> Result<String, Error> {
+        let modified = Self::modified(&path);
+        let contents = Self::read_file_to_string_uncached(&path, encoding);
+        self.record_reads(&[(path, modified)]);
+        contents
+    }
+
+    fn read_file_to_string_uncached(path: &Path, encoding: Option<&str>) -> Result<String, Error> {
         let mut file = File::open(path.as_os_str())?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-        Ok(contents)
+        match encoding {
+            None => {
+                let mut contents = String::new();
+                file.read_to_string(&mut contents)?;
+                Ok(contents)
+            }
+            Some(encoding) => {
+                let mut contents = vec![];
+                file.read_to_end(&mut contents)?;
+                Self::decode(&contents, encoding)
+            }
+        }
+    }
+
+    #[cfg(feature = "encoding")]
+    fn decode(contents: &[u8], encoding: &str) -> Result<String, Error> {
+        let encoding =
+            encoding_rs::Encoding::for_label(encoding.as_bytes()).ok_or_else(|| Error::Io {
+                message: format!("unknown encoding '{}'", encoding),
+            })?;
+        Ok(encoding.decode(contents).0.into_owned())
+    }
+
+    #[cfg(not(feature = "encoding"))]
+    fn decode(contents: &[u8], encoding: &str) -> Result<String, Error> {
+        match encoding.to_lowercase().as_str() {
+            "utf-8" | "utf8" => String::from_utf8(contents.to_vec()).map_err(|err| Error::Io {
+                message: err.to_string(),
+            }),
+            _ => Err(Error::Io {
+                message: format!("encoding '{}' needs feature 'encoding'", encoding),
+            }),
+        }
     }
 
     pub(crate) fn read_file(&self) -> Result<FileRead, Error> {
+        self.read_file_with_encoding(None)
+    }
+
+    pub(crate) fn read_file_with_encoding(
+        &self,
+        encoding: Option<&str>,
+    ) -> Result<FileRead, Error> {
         let full_path = self
             .file_meta
             .clone()
             .expect("missing file metadata")
             .full_path;
         match self.file_meta.as_ref().map(|fm| &fm.file_type) {
-            Some(FileType::All) => Ok(FileRead {
-                hocon: Self::read_file_to_string({
-                    let mut path = full_path.clone();
-                    if !path.exists() {
-                        path.set_extension("conf");
-                    }
-                    path
-                })
-                .ok(),
-                json: Self::read_file_to_string({
-                    let mut path = full_path.clone();
-                    path.set_extension("json");
-                    path
-                })
-                .ok(),
-                properties: Self::read_file_to_string({
-                    let mut path = full_path;
-                    path.set_extension("properties");
-                    path
-                })
-                .ok(),
+            Some(None) => Ok(FileRead {
+                hocon: self
+                    .read_file_to_string(
+                        {
+                            let mut path = full_path.clone();
+                            if !path.exists() {
+                                path.set_extension("conf");
+                            }
+                            path
+                        },
+                        encoding,
+                    )
+                    .ok(),
+                json: self
+                    .read_file_to_string(
+                        {
+                            let mut path = full_path.clone();
+                            path.set_extension("json");
+                            path
+                        },
+                        encoding,
+                    )
+                    .ok(),
+                properties: self
+                    .read_file_to_string(
+                        {
+                            let mut path = full_path;
+                            path.set_extension("properties");
+                            path
+                        },
+                        encoding,
+                    )
+                    .ok(),
             }),
-            Some(ft) => Ok(FileRead::from_file_type(
+            Some(Some(ft)) => Ok(FileRead::from_file_type(
                 ft,
-                Self::read_file_to_string(full_path)?,
+                self.read_file_to_string(full_path, encoding)?,
             )),
             _ => unimplemented!(),
         }
@@ -226,39 +596,71 @@ impl HoconLoaderConfig {
     pub(crate) fn load_url(&self, url: &str) -> Result<crate::internals::HoconInternal, Error> {
         if let Ok(parsed_url) = reqwest::Url::parse(url) {
             if parsed_url.scheme() == "file" {
-                if let Ok(path) = parsed_url.to_file_path() {
-                    let include_config = self.included_from().with_file(path);
+                if !self.file_include {
+                    Err(self.include_error(url))
+                } else if let Ok(path) = parsed_url.to_file_path() {
+                    let include_config = self.included_from(url).with_file(path);
                     let s = include_config.read_file()?;
-                    Ok(include_config.parse_str_to_internal(s).map_err(|_| {
-                        crate::Error::Include {
-                            path: String::from(url),
-                        }
-                    })?)
+                    Ok(include_config
+                        .parse_str_to_internal(s)
+                        .map_err(|_| self.include_error(url))?)
                 } else {
-                    Err(crate::Error::Include {
-                        path: String::from(url),
-                    })
+                    Err(self.include_error(url))
                 }
             } else if self.external_url {
                 let body = reqwest::blocking::get(parsed_url)
                     .and_then(reqwest::blocking::Response::text)
-                    .map_err(|_| crate::Error::Include {
-                        path: String::from(url),
-                    })?;
+                    .map_err(|_| self.include_error(url))?;
 
                 Ok(self.parse_str_to_internal(FileRead {
                     hocon: Some(body),
                     ..Default::default()
                 })?)
             } else {
-                Err(crate::Error::Include {
-                    path: String::from(url),
-                })
+                Err(self.include_error(url))
             }
         } else {
-            Err(crate::Error::Include {
-                path: String::from(url),
-            })
+            Err(self.include_error(url))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_document_is_not_parsed_again() {
+        let dir = std::env::temp_dir().join(format!("hocon-parse-cache-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("during test");
+        let path = dir.join("cached.conf");
+        std::fs::write(&path, "a = 1").expect("during test");
+        let cache = Arc::new(FileCache::new());
+        let load = || {
+            crate::HoconLoader::new()
+                .with_shared_cache(Arc::clone(&cache))
+                .load_file(&path)
+                .expect("during test")
+                .hocon()
+                .expect("during test")
+        };
+        assert_eq!(load()["a"], crate::Hocon::Integer(1));
+
+        // only the cached document has this value, the file would give back `a = 1`
+        let parsed = HoconLoaderConfig::default()
+            .parse_str_to_internal(FileRead::from_file_type(
+                &FileType::Hocon,
+                String::from("a = 2"),
+            ))
+            .expect("during test");
+        cache
+            .files
+            .lock()
+            .expect("during test")
+            .values_mut()
+            .for_each(|cached| cached.internal = parsed.clone());
+        assert_eq!(load()["a"], crate::Hocon::Integer(2));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,39 @@
+/// Build an [`Hocon`](enum.Hocon.html) document from a JSON-like literal
+///
+/// Keys must be string literals. Values can be `null`, literals, nested objects and arrays,
+/// or any expression that can be converted to an `Hocon` with `From`. Expressions made of
+/// more than one token, like `-1` or `a + b`, must be wrapped in parentheses.
+///
+/// ```rust
+/// # use hocon::{hocon, Hocon};
+/// let doc = hocon!({
+///     "name": "server",
+///     "port": 8080,
+///     "offset": (-1),
+///     "tags": ["a", "b"],
+///     "tls": { "enabled": true, "cert": null }
+/// });
+///
+/// assert_eq!(doc["port"], Hocon::Integer(8080));
+/// assert_eq!(doc["tags"][1], Hocon::String(String::from("b")));
+/// assert_eq!(doc["tls"]["cert"], Hocon::Null);
+/// ```
+#[macro_export]
+macro_rules! hocon {
+    (null) => {
+        $crate::Hocon::Null
+    };
+    ([ $( $value:tt ),* $(,)? ]) => {
+        $crate::Hocon::Array(vec![ $( $crate::hocon!($value) ),* ])
+    };
+    ({ $( $key:literal : $value:tt ),* $(,)? }) => {
+        $crate::Hocon::Hash(
+            vec![ $( (::std::string::String::from($key), $crate::hocon!($value)) ),* ]
+                .into_iter()
+                .collect(),
+        )
+    };
+    ($value:expr) => {
+        $crate::Hocon::from($value)
+    };
+}
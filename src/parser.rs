@@ -73,6 +73,17 @@ impl std::str::FromStr for F64WithoutLeadingDot {
     }
 }
 
+// Keep the original text of real numbers next to their value, like `1.0` or `1e3`, so that
+// they can be rendered the way they were written
+named!(
+    real_with_text<HoconValue>,
+    map_res!(recognize_float, |number: &[u8]| {
+        let text = str::from_utf8(number).map_err(|_| ())?;
+        text.parse::<F64WithoutLeadingDot>()
+            .map(|f| HoconValue::Real(f.0, Some(String::from(text))))
+    })
+);
+
 named!(null, tag!("null"));
 
 //FIXME: verify how json strings are formatted
@@ -165,6 +176,23 @@ named!(
     alt!(value!(false, tag!("false")) | value!(true, tag!("true")))
 );
 
+// Position of the first of the `stops` in `input`. A `//` only starts a comment at the start
+// of the input or after a whitespace, and a `:` followed by `//` doesn't stop the input, so
+// that urls like `http://x` are kept whole
+fn find_unquoted_string_stop(input: &[u8], stops: &[&str]) -> Option<usize> {
+    let is_stop = |bytes: &[u8]| matches!(str::from_utf8(bytes), Ok(s) if stops.contains(&s));
+    (0..input.len()).find(|&i| {
+        let rest = &input[i..];
+        if rest.starts_with(b"//") {
+            is_stop(b"//") && (i == 0 || input[i - 1].is_ascii_whitespace())
+        } else if rest.starts_with(b"://") {
+            false
+        } else {
+            is_stop(&rest[..1])
+        }
+    })
+}
+
 macro_rules! take_until_tag1 (
     ($input:expr, $arr:expr) => (
         {
@@ -172,61 +200,79 @@ macro_rules! take_until_tag1 (
             use nom::lib::std::option::Option::*;
             use nom::{Err,Needed,IResult,need_more_err,ErrorKind};
 
-            use nom::InputIter;
             use nom::InputTake;
 
-            let res: IResult<_, _> = match $input.iter_indices()
-                .fold((0, None),
-                    |(old_c, pos), (i, c)| {
-                        if pos.is_some() {
-                            (old_c, pos)
-                        } else if $arr.contains(&str::from_utf8(&[c]).unwrap()) {
-                            (c, Some(i))
-                        } else {
-                            if $arr.contains(&str::from_utf8(&[old_c, c]).unwrap()) {
-                                (c, Some(i - 1))
-                            } else {
-                                (c, None)
-                            }
-                        }
-                    }
-                )
-            {
-                (_, Some(0)) => Err(Err::Error(error_position!($input, ErrorKind::TakeUntilEither::<u32>))),
-                (_, Some(n)) => Ok($input.take_split(n)),
-                (_, None)    => need_more_err($input, Needed::Size(1), ErrorKind::TakeUntilEither::<u32>)
+            let res: IResult<_, _> = match find_unquoted_string_stop($input, $arr) {
+                Some(0) => Err(Err::Error(error_position!($input, ErrorKind::TakeUntilEither::<u32>))),
+                Some(n) => Ok($input.take_split(n)),
+                None    => need_more_err($input, Needed::Size(1), ErrorKind::TakeUntilEither::<u32>)
             };
             res
         }
     );
 );
 
-named!(
-    unquoted_string<&str>,
+// Characters that end an unquoted string
+pub(crate) const UNQUOTED_STRING_STOP: &[&str] = &[
+    "$", "\"", "{", "}", "[", "]", ":", "=", ",", "+", "#", "`", "^", "?", "!", "@", "*", "&", "'",
+    "\\", "\t", "\n", "//",
+];
+
+named_args!(
+    unquoted_string<'a>(config: &HoconLoaderConfig)<String>,
     map_res!(
-        complete!(take_until_tag1!([
-            "$", "\"", "{", "}", "[", "]", ":", "=", ",", "+", "#", "`", "^", "?", "!", "@", "*",
-            "&", "'", "\\", "\t", "\n", "//"
-        ])),
-        str::from_utf8
+        complete!(take_until_tag1!(config.unquoted_string_stop.as_slice())),
+        |s| str::from_utf8(s).map(String::from)
     )
 );
 
-named!(
-    path_substitution<HoconValue>,
-    delimited!(alt!(tag!("${?") | tag!("${")), value, char!('}'))
+// An unquoted key followed by a space, for keys and values separated by spaces only
+fn space_separated_key<'a>(
+    input: &'a [u8],
+    config: &HoconLoaderConfig,
+) -> IResult<&'a [u8], String> {
+    let stop = config
+        .unquoted_string_stop
+        .iter()
+        .cloned()
+        .chain(std::iter::once(" "))
+        .collect::<Vec<_>>();
+    map_res!(
+        input,
+        terminated!(
+            complete!(take_until_tag1!(stop.as_slice())),
+            peek!(one_of!(" \t"))
+        ),
+        |s| str::from_utf8(s).map(String::from)
+    )
+}
+
+named_args!(
+    path_substitution<'a>(config: &HoconLoaderConfig)<HoconValue>,
+    delimited!(alt!(tag!("${?") | tag!("${")), call!(value, config), char!('}'))
 );
 
-named!(
-    optional_path_substitution<HoconValue>,
-    delimited!(tag!("${?"), value, char!('}'))
+named_args!(
+    optional_path_substitution<'a>(config: &HoconLoaderConfig)<(HoconValue, Option<HoconValue>)>,
+    delimited!(tag!("${?"), pair!(call!(value, config), opt!(call!(substitution_default, config))), char!('}'))
+);
+
+// A path substitution with an optional inline default, as in `${target:-default}`
+named_args!(
+    path_substitution_with_default<'a>(config: &HoconLoaderConfig)<(HoconValue, Option<HoconValue>)>,
+    delimited!(tag!("${"), pair!(call!(value, config), opt!(call!(substitution_default, config))), char!('}'))
+);
+
+named_args!(
+    substitution_default<'a>(config: &HoconLoaderConfig)<HoconValue>,
+    preceded!(tag!(":-"), call!(value, config))
 );
 
 named_args!(
     arrays<'a>(config: &HoconLoaderConfig)<Result<Vec<HoconInternal>, crate::Error>>,
     map!(
         do_parse!(
-            maybe_substitution: opt!(path_substitution)
+            maybe_substitution: opt!(call!(path_substitution, config))
                 >> first_array: call!(array, config)
                 >> remaining_arrays: many0!(call!(array, config))
                 >> (maybe_substitution, first_array, remaining_arrays)
@@ -267,17 +313,17 @@ named_args!(
                 separated_pair!(ws!(string), ws!(alt!(char!(':') | char!('='))), call!(wrapper, config))
                     => { |(s, h): (Cow<str>, Result<HoconInternal, _>)|
                         Ok(HoconInternal::from_object(h?.internal)
-                            .add_to_path(vec![HoconValue::String(s.to_string())]).internal)
+                            .add_to_path(vec![HoconValue::String(s.into())]).internal)
                     } |
                 pair!(ws!(string), call!(hashes, config))
                     => { |(s, h): (Cow<str>, Result<Hash, _>)|
                         Ok(HoconInternal::from_object(h?)
-                            .add_to_path(vec![HoconValue::String(s.to_string())]).internal)
+                            .add_to_path(vec![HoconValue::String(s.into())]).internal)
                     } |
                 // to concat to an array
                 separated_pair!(ws!(string), ws!(tag!("+=")), call!(wrapper, config))
                     => { |(s, h): (Cow<str>, Result<HoconInternal, _>)| {
-                            let item_id = uuid::Uuid::new_v4().hyphenated().to_string();
+                            let item_id = config.next_item_id();
                             Ok(HoconInternal::from_object(h?.internal)
                                 .transform(|k, v| (
                                     k.clone(),
@@ -287,23 +333,23 @@ named_args!(
                                         item_id: item_id.clone(),
                                     }
                                 ))
-                                .add_to_path(vec![HoconValue::String(s.to_string())]).internal)
+                                .add_to_path(vec![HoconValue::String(s.into())]).internal)
                         }
                     } |
-                separated_pair!(ws!(unquoted_string), ws!(alt!(char!(':') | char!('='))), call!(wrapper, config))
-                    => { |(s, h): (&str, Result<HoconInternal, _>)|
+                separated_pair!(ws!(call!(unquoted_string, config)), ws!(alt!(char!(':') | char!('='))), call!(wrapper, config))
+                    => { |(s, h): (String, Result<HoconInternal, _>)|
                         Ok(HoconInternal::from_object(h?.internal)
-                            .add_to_path(vec![HoconValue::UnquotedString(String::from(s))]).internal)
+                            .add_to_path(vec![HoconValue::UnquotedString(s.into())]).internal)
                     } |
-                pair!(ws!(unquoted_string), call!(hashes, config))
-                    => { |(s, h): (&str, Result<Hash, _>)|
+                pair!(ws!(call!(unquoted_string, config)), call!(hashes, config))
+                    => { |(s, h): (String, Result<Hash, _>)|
                         Ok(HoconInternal::from_object(h?)
-                            .add_to_path(vec![HoconValue::UnquotedString(String::from(s))]).internal)
+                            .add_to_path(vec![HoconValue::UnquotedString(s.into())]).internal)
                     } |
                 // to concat to an array
-                separated_pair!(ws!(unquoted_string), ws!(tag!("+=")), call!(wrapper, config))
-                    => { |(s, h): (&str, Result<HoconInternal, _>)| {
-                            let item_id = uuid::Uuid::new_v4().hyphenated().to_string();
+                separated_pair!(ws!(call!(unquoted_string, config)), ws!(tag!("+=")), call!(wrapper, config))
+                    => { |(s, h): (String, Result<HoconInternal, _>)| {
+                            let item_id = config.next_item_id();
                             Ok(HoconInternal::from_object(h?.internal)
                                 .transform(|k, v| (
                                     k.clone(),
@@ -313,8 +359,15 @@ named_args!(
                                         item_id: item_id.clone(),
                                     }
                                 ))
-                                .add_to_path(vec![HoconValue::UnquotedString(String::from(s))]).internal)
+                                .add_to_path(vec![HoconValue::UnquotedString(s.into())]).internal)
                         }
+                    } |
+                // key and value separated by spaces only, tried last as `key {` and `key =` take
+                // precedence
+                cond_reduce!(config.allow_space_separator, pair!(call!(space_separated_key, config), preceded!(space, call!(wrapper, config))))
+                    => { |(s, h): (String, Result<HoconInternal, _>)|
+                        Ok(HoconInternal::from_object(h?.internal)
+                            .add_to_path(vec![HoconValue::UnquotedString(s.into())]).internal)
                     }
             ))
             >> (pair)
@@ -347,20 +400,23 @@ named_args!(
     hashes<'a>(config: &HoconLoaderConfig)<Result<Hash, crate::Error>>,
     map!(
         do_parse!(
-            maybe_substitution: opt!(path_substitution)
+            maybe_substitution: opt!(call!(path_substitution, config))
                 >> first_hash: call!(hash, config)
                 >> remaining_hashes: many0!(call!(hash, config))
                 >> (maybe_substitution, first_hash, remaining_hashes)
         ),
         |(maybe_substitution, first_hash, remaining_hashes)| match (maybe_substitution, remaining_hashes.is_empty()) {
-            (None, true) => first_hash,
-            (None, false) => {
+            (None, _) => {
                 let mut values = first_hash?;
                 crate::helper::extract_result(remaining_hashes)?.into_iter().for_each(|mut hash| values.append(&mut hash));
+                // mark where this object starts, an empty object being already kept as is
+                if !values.is_empty() {
+                    values.insert(0, (vec![], HoconValue::ObjectStart));
+                }
                 Ok(values)
             }
             (Some(subst), _) => {
-                let mut values = vec![(vec![], HoconValue::PathSubstitution{target: Box::new(subst), optional: false, original: None})];
+                let mut values = vec![(vec![], HoconValue::PathSubstitution{target: Box::new(subst), optional: false, original: None, default: None})];
                 values.append(&mut first_hash?);
                 crate::helper::extract_result(remaining_hashes)?.into_iter().for_each(|mut hash| values.append(&mut hash));
                 Ok(values)
@@ -380,34 +436,37 @@ named_args!(
 named_args!(
     root_hash<'a>(config: &HoconLoaderConfig)<Result<Hash, crate::Error>>,
     sp!(map!(
-        do_parse!(not!(char!('{')) >> list: call!(separated_hashlist, config) >> (list)),
+        do_parse!(not!(one_of!("{[")) >> list: call!(separated_hashlist, config) >> (list)),
         |tuple_vec| Ok(tuple_vec?.into_iter().flat_map(std::iter::IntoIterator::into_iter).collect())
     ))
 );
 
-named!(
-    single_value<HoconValue>,
+named_args!(
+    single_value<'a>(config: &HoconLoaderConfig)<HoconValue>,
     alt!(
-        multiline_string =>  { |s| HoconValue::String(String::from(s))         } |
-        string  =>           { |s| HoconValue::String(String::from(s))         } |
+        multiline_string =>  { |s: &str| HoconValue::String(s.into())           } |
+        string  =>           { |s: Cow<str>| HoconValue::String(s.into())      } |
         integer =>           { HoconValue::Integer                      } |
-        float   =>           { HoconValue::Real                         } |
+        cond_reduce!(config.preserve_number_text, real_with_text) => { |v| v } |
+        float   =>           { |f| HoconValue::Real(f, None)            } |
         boolean =>           { HoconValue::Boolean                      } |
-        optional_path_substitution =>
-            { |p| HoconValue::PathSubstitution{target: Box::new(p), optional: true, original: None}  } |
-        path_substitution =>
-            { |p| HoconValue::PathSubstitution{target: Box::new(p), optional: false, original: None} } |
-        unquoted_string =>   { |s| HoconValue::UnquotedString(String::from(s)) }
+        call!(optional_path_substitution, config) =>
+            { |(p, d): (HoconValue, Option<HoconValue>)|
+                HoconValue::PathSubstitution{target: Box::new(p), optional: true, original: None, default: d.map(Box::new)}  } |
+        call!(path_substitution_with_default, config) =>
+            { |(p, d): (HoconValue, Option<HoconValue>)|
+                HoconValue::PathSubstitution{target: Box::new(p), optional: false, original: None, default: d.map(Box::new)} } |
+        call!(unquoted_string, config) => { |s: String| HoconValue::UnquotedString(s.into()) }
     )
 );
 
-named!(
-    value<HoconValue>,
+named_args!(
+    value<'a>(config: &HoconLoaderConfig)<HoconValue>,
     map!(
         do_parse!(
             possible_comment
-                >> first_value: single_value
-                >> remaining_values: many0!(single_value)
+                >> first_value: call!(single_value, config)
+                >> remaining_values: many0!(call!(single_value, config))
                 >> (first_value, remaining_values)
         ),
         |(first_value, mut remaining_values)| if remaining_values.is_empty() {
@@ -420,6 +479,31 @@ named!(
     )
 );
 
+named!(
+    include_alias<HoconValue>,
+    do_parse!(
+        tag!("as")
+            // at least one space or tab between `as` and the alias
+            >> verify!(recognize!(space), |s: &[u8]| !s.is_empty())
+            >> alias:
+                alt!(
+                    map!(string, |s| HoconValue::String(s.into()))
+                        | map!(
+                            map_res!(
+                                take_while1!(|c: u8| c.is_ascii_alphanumeric()
+                                    || c == b'_'
+                                    || c == b'-'
+                                    || c == b'.'),
+                                str::from_utf8
+                            ),
+                            |s| HoconValue::UnquotedString(s.into())
+                        )
+                )
+            >> space
+            >> (alias)
+    )
+);
+
 named!(
     include<Include>,
     do_parse!(
@@ -427,18 +511,26 @@ named!(
             >> ws!(many0!(newline))
             >> included:
                 sp!(alt!(
-                    map!(call!(string), Include::File)
+                    map!(call!(string), |file_name| Include::File(file_name, None, None))
                         | do_parse!(
                             tag!("file(")
                                 >> file_name: string
+                                >> encoding: opt!(do_parse!(ws!(char!(',')) >> encoding: string >> (encoding)))
                                 >> tag!(")")
-                                >> (Include::File(file_name))
+                                >> (Include::File(file_name, encoding, None))
                         )
                         | do_parse!(
-                            tag!("url(") >> url: string >> tag!(")") >> (Include::Url(url))
+                            tag!("url(") >> url: string >> tag!(")") >> (Include::Url(url, None))
+                        )
+                        | do_parse!(
+                            tag!("classpath(")
+                                >> name: string
+                                >> tag!(")")
+                                >> (Include::Classpath(name, None))
                         )
                 ))
-            >> (included)
+            >> alias: opt!(complete!(include_alias))
+            >> (included.with_alias(alias))
     )
 );
 
@@ -457,9 +549,9 @@ named_args!(
             >> wrapped:
                 alt!(
                     call!(hashes, config) => { |h| Ok(HoconInternal::from_object(h?))     } |
-                    call!(arrays, config) => { |a| Ok(HoconInternal::from_array(a?))      } |
+                    call!(arrays, config) => { |a| Ok(HoconInternal::from_array(a?, config))      } |
                     include               => { |f| HoconInternal::from_include(f, config) } |
-                    value                 => { |v| Ok(HoconInternal::from_value(v))       }
+                    call!(value, config)  => { |v| Ok(HoconInternal::from_value(v))       }
                 )
             >> (wrapped)
     )
@@ -474,7 +566,7 @@ named_args!(
                     call!(root_include, config) => { |d| d                                  } |
                     call!(root_hash, config)    => { |h| Ok(HoconInternal::from_object(h?)) } |
                     call!(hash, config)         => { |h| Ok(HoconInternal::from_object(h?)) } |
-                    call!(array, config)        => { |a| Ok(HoconInternal::from_array(a?))  }
+                    call!(array, config)        => { |a| Ok(HoconInternal::from_array(a?, config))  }
                 )
             >> possible_comment
             >> (wrapped)
@@ -2,6 +2,7 @@
 
 use super::error::{Error, Result};
 use crate::Hocon;
+use std::convert::TryFrom;
 
 pub use super::wrappers;
 
@@ -19,15 +20,11 @@ macro_rules! impl_deserialize_n {
                         message: format!("missing integer for field \"{}\"", self.current_field),
                     })?
                     .clone();
+                self.check_strict_type(&value, |v| matches!(v, Hocon::Integer(_)), "integer")?;
                 value
                     .as_i64()
                     .or_else(|| value.as_bytes().map(|v| v as i64))
-                    .ok_or_else(|| Error {
-                        message: format!(
-                            "Invalid type for field \"{}\", expected integer",
-                            self.current_field
-                        ),
-                    })?
+                    .ok_or_else(|| self.invalid_type("integer", &value))?
             })
         }
     };
@@ -44,15 +41,11 @@ macro_rules! impl_deserialize_n {
                         message: format!("missing integer for field \"{}\"", self.current_field),
                     })?
                     .clone();
+                self.check_strict_type(&value, |v| matches!(v, Hocon::Integer(_)), "integer")?;
                 value
                     .as_i64()
                     .or_else(|| value.as_bytes().map(|v| v as i64))
-                    .ok_or_else(|| Error {
-                        message: format!(
-                            "Invalid type for field \"{}\", expected integer",
-                            self.current_field
-                        ),
-                    })? as $type
+                    .ok_or_else(|| self.invalid_type("integer", &value))? as $type
             })
         }
     };
@@ -71,15 +64,15 @@ macro_rules! impl_deserialize_f {
                         message: format!("missing float for field \"{}\"", self.current_field),
                     })?
                     .clone();
+                self.check_strict_type(
+                    &value,
+                    |v| matches!(v, Hocon::Real(_) | Hocon::Integer(_)),
+                    "float",
+                )?;
                 value
                     .as_f64()
                     .or_else(|| value.as_bytes().map(|v| v as f64))
-                    .ok_or_else(|| Error {
-                        message: format!(
-                            "Invalid type for field \"{}\", expected float",
-                            self.current_field
-                        ),
-                    })?
+                    .ok_or_else(|| self.invalid_type("float", &value))?
             })
         }
     };
@@ -96,15 +89,15 @@ macro_rules! impl_deserialize_f {
                         message: format!("missing float for field \"{}\"", self.current_field),
                     })?
                     .clone();
+                self.check_strict_type(
+                    &value,
+                    |v| matches!(v, Hocon::Real(_) | Hocon::Integer(_)),
+                    "float",
+                )?;
                 value
                     .as_f64()
                     .or_else(|| value.as_bytes().map(|v| v as f64))
-                    .ok_or_else(|| Error {
-                        message: format!(
-                            "Invalid type for field \"{}\", expected float",
-                            self.current_field
-                        ),
-                    })? as $type
+                    .ok_or_else(|| self.invalid_type("float", &value))? as $type
             })
         }
     };
@@ -168,16 +161,48 @@ struct Deserializer<R> {
     read: R,
     current_field: Index,
     as_key: bool,
+    strict_types: bool,
 }
 impl<'de, R> Deserializer<R>
 where
     R: Read,
 {
-    pub fn new(read: R) -> Self {
+    pub fn new(read: R, strict_types: bool) -> Self {
         Deserializer {
             read,
             current_field: Index::None,
             as_key: false,
+            strict_types,
+        }
+    }
+
+    fn nested<N: Read>(&self, read: N) -> Deserializer<N> {
+        Deserializer::new(read, self.strict_types)
+    }
+
+    // With strict types, values must already be of the expected type instead of going
+    // through automatic type conversions
+    fn check_strict_type(
+        &self,
+        value: &Hocon,
+        is_expected_type: fn(&Hocon) -> bool,
+        expected: &str,
+    ) -> Result<()> {
+        if self.strict_types && !is_expected_type(value) {
+            Err(self.invalid_type(expected, value))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn invalid_type(&self, expected: &str, found: &Hocon) -> Error {
+        Error {
+            message: format!(
+                "Invalid type for field \"{}\", expected {}, found {}",
+                self.current_field,
+                expected,
+                found.type_name()
+            ),
         }
     }
 }
@@ -218,20 +243,18 @@ impl<'de, 'a, R: Read> serde::de::Deserializer<'de> for &'a mut Deserializer<R>
     where
         V: serde::de::Visitor<'de>,
     {
+        let value = self
+            .read
+            .get_attribute_value(&self.current_field)
+            .ok_or_else(|| Error {
+                message: format!("Missing field \"{}\"", self.current_field),
+            })?
+            .clone();
+        self.check_strict_type(&value, |v| matches!(v, Hocon::Boolean(_)), "bool")?;
         visitor.visit_bool(
-            self.read
-                .get_attribute_value(&self.current_field)
-                .ok_or_else(|| Error {
-                    message: format!("Missing field \"{}\"", self.current_field),
-                })?
-                .clone()
+            value
                 .as_bool()
-                .ok_or_else(|| Error {
-                    message: format!(
-                        "Invalid type for field \"{}\", expected bool",
-                        self.current_field
-                    ),
-                })?,
+                .ok_or_else(|| self.invalid_type("bool", &value))?,
         )
     }
 
@@ -282,12 +305,10 @@ impl<'de, 'a, R: Read> serde::de::Deserializer<'de> for &'a mut Deserializer<R>
                 _ => visitor.visit_str(""),
             }
         } else if let Some(field) = self.read.get_attribute_value(&self.current_field) {
+            self.check_strict_type(field, |v| matches!(v, Hocon::String(_)), "string")?;
             field
-                .clone()
                 .as_string()
-                .ok_or_else(|| Error {
-                    message: format!("missing string for field \"{}\"", self.current_field),
-                })
+                .ok_or_else(|| self.invalid_type("string", field))
                 .and_then(|string_field| visitor.visit_str(&string_field))
         } else {
             visitor.visit_str("")
@@ -301,18 +322,36 @@ impl<'de, 'a, R: Read> serde::de::Deserializer<'de> for &'a mut Deserializer<R>
         self.deserialize_str(visitor)
     }
 
-    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
     where
         V: serde::de::Visitor<'de>,
     {
-        unimplemented!()
+        self.deserialize_byte_buf(visitor)
     }
 
-    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value>
+    // Bytes are read from an array of integers between 0 and 255, or from the UTF-8
+    // representation of a string
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
     where
         V: serde::de::Visitor<'de>,
     {
-        unimplemented!()
+        let value = self
+            .read
+            .get_attribute_value(&self.current_field)
+            .ok_or_else(|| Error {
+                message: format!("missing bytes for field \"{}\"", self.current_field),
+            })?;
+        let invalid = || self.invalid_type("bytes", value);
+        let bytes = match value {
+            Hocon::String(string) => string.clone().into_bytes(),
+            Hocon::Array(values) => values
+                .iter()
+                .map(|value| value.as_i64().and_then(|byte| u8::try_from(byte).ok()))
+                .collect::<Option<Vec<u8>>>()
+                .ok_or_else(invalid)?,
+            _ => return Err(invalid()),
+        };
+        visitor.visit_byte_buf(bytes)
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
@@ -337,14 +376,9 @@ impl<'de, 'a, R: Read> serde::de::Deserializer<'de> for &'a mut Deserializer<R>
     where
         V: serde::de::Visitor<'de>,
     {
-        match self
-            .read
-            .get_attribute_value(&self.current_field)
-            .ok_or_else(|| Error {
-                message: format!("missing option for field \"{}\"", self.current_field),
-            })? {
-            Hocon::Null => visitor.visit_unit(),
-            _ => visitor.visit_unit(),
+        match self.read.get_attribute_value(&self.current_field) {
+            None | Some(Hocon::Null) => visitor.visit_unit(),
+            Some(value) => Err(self.invalid_type("null", value)),
         }
     }
 
@@ -384,7 +418,7 @@ impl<'de, 'a, R: Read> serde::de::Deserializer<'de> for &'a mut Deserializer<R>
                 });
             }
         };
-        let mut des = Deserializer::new(read);
+        let mut des = self.nested(read);
         visitor.visit_seq(SeqAccess::new(&mut des))
     }
 
@@ -410,7 +444,7 @@ impl<'de, 'a, R: Read> serde::de::Deserializer<'de> for &'a mut Deserializer<R>
                 });
             }
         };
-        let mut des = Deserializer::new(read);
+        let mut des = self.nested(read);
         visitor.visit_seq(SeqAccess::new(&mut des))
     }
 
@@ -448,7 +482,7 @@ impl<'de, 'a, R: Read> serde::de::Deserializer<'de> for &'a mut Deserializer<R>
                         })
                     }
                 };
-                let mut des = Deserializer::new(HoconRead::new(hc));
+                let mut des = self.nested(HoconRead::new(hc));
                 visitor.visit_map(MapAccess::new(&mut des, keys))
             }
         }
@@ -487,7 +521,7 @@ impl<'de, 'a, R: Read> serde::de::Deserializer<'de> for &'a mut Deserializer<R>
             for v in variants {
                 if s == v {
                     let reader = HoconRead::new(hc);
-                    let deserializer = &mut Deserializer::new(reader);
+                    let deserializer = &mut self.nested(reader);
                     deserializer.current_field = Index::String(String::from(s));
                     return visitor.visit_enum(UnitVariantAccess::new(deserializer));
                 }
@@ -498,7 +532,7 @@ impl<'de, 'a, R: Read> serde::de::Deserializer<'de> for &'a mut Deserializer<R>
             Hocon::String(name) => {
                 let index = Index::String(String::from(name));
                 let reader = HoconRead::new(hc);
-                let deserializer = &mut Deserializer::new(reader);
+                let deserializer = &mut self.nested(reader);
                 deserializer.current_field = index;
                 visitor.visit_enum(UnitVariantAccess::new(deserializer))
             }
@@ -520,7 +554,7 @@ impl<'de, 'a, R: Read> serde::de::Deserializer<'de> for &'a mut Deserializer<R>
                 }
                 let index = Index::String(String::from(first_key));
                 let reader = HoconRead::new(hc);
-                let deserializer = &mut Deserializer::new(reader);
+                let deserializer = &mut self.nested(reader);
                 deserializer.current_field = index;
                 visitor.visit_enum(VariantAccess::new(deserializer))
             }
@@ -735,12 +769,12 @@ impl<'de, 'a, R: Read + 'a> serde::de::VariantAccess<'de> for UnitVariantAccess<
     }
 }
 
-fn from_trait<'de, R, T>(read: R) -> Result<T>
+fn from_trait<'de, R, T>(read: R, strict_types: bool) -> Result<T>
 where
     R: Read,
     T: serde::de::Deserialize<'de>,
 {
-    let mut de = Deserializer::new(read);
+    let mut de = Deserializer::new(read, strict_types);
     let value = serde_path_to_error::deserialize(&mut de)?;
 
     Ok(value)
@@ -750,7 +784,14 @@ pub(crate) fn from_hocon<'de, T>(hocon: Hocon) -> Result<T>
 where
     T: serde::de::Deserialize<'de>,
 {
-    from_trait(HoconRead::new(hocon))
+    from_trait(HoconRead::new(hocon), false)
+}
+
+pub(crate) fn from_hocon_with_strict_types<'de, T>(hocon: Hocon) -> Result<T>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    from_trait(HoconRead::new(hocon), true)
 }
 
 /// Deserialize a HOCON string directly
@@ -758,9 +799,10 @@ pub fn from_str<'de, T>(hocon: &str) -> std::result::Result<T, crate::Error>
 where
     T: serde::de::Deserialize<'de>,
 {
-    from_trait(HoconRead::new(
-        crate::HoconLoader::new().load_str(hocon)?.hocon()?,
-    ))
+    from_trait(
+        HoconRead::new(crate::HoconLoader::new().load_str(hocon)?.hocon()?),
+        false,
+    )
     .map_err(|err| crate::Error::Deserialization {
         message: err.message,
     })
@@ -831,6 +873,37 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn error_reports_found_type() {
+        #[derive(Deserialize, Debug)]
+        struct WithBoolAndString {
+            boolean: bool,
+            string: String,
+        }
+
+        let mut hm = LinkedHashMap::new();
+        hm.insert(String::from("boolean"), Hocon::Array(vec![]));
+        hm.insert(String::from("string"), Hocon::String(String::from("test")));
+        let res: super::Result<WithBoolAndString> = super::from_hocon(Hocon::Hash(hm));
+        assert_eq!(
+            res.map(|_| ()).map_err(|err| err.message),
+            Err(String::from(
+                "boolean: Invalid type for field \"boolean\", expected bool, found array"
+            ))
+        );
+
+        let mut hm = LinkedHashMap::new();
+        hm.insert(String::from("boolean"), Hocon::Boolean(true));
+        hm.insert(String::from("string"), Hocon::Hash(LinkedHashMap::new()));
+        let res: super::Result<WithBoolAndString> = super::from_hocon(Hocon::Hash(hm));
+        assert_eq!(
+            res.map(|_| ()).map_err(|err| err.message),
+            Err(String::from(
+                "string: Invalid type for field \"string\", expected string, found hash"
+            ))
+        );
+    }
+
     #[test]
     fn will_not_fail_on_extra_field() {
         let mut hm = LinkedHashMap::new();
@@ -843,6 +916,110 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[derive(Deserialize, Debug)]
+    #[serde(deny_unknown_fields)]
+    struct SimpleWithoutExtra {
+        int: i64,
+        float: f64,
+    }
+
+    // Deserialize through `deserialize_byte_buf`, like `serde_bytes` would
+    #[derive(Debug, PartialEq)]
+    struct Bytes(Vec<u8>);
+    impl<'de> Deserialize<'de> for Bytes {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct BytesVisitor;
+            impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+                type Value = Bytes;
+                fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    formatter.write_str("bytes")
+                }
+                fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                    Ok(Bytes(v))
+                }
+            }
+            deserializer.deserialize_byte_buf(BytesVisitor)
+        }
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct WithBytes {
+        bytes: Bytes,
+    }
+
+    #[test]
+    fn can_deserialize_bytes() {
+        let mut hm = LinkedHashMap::new();
+        hm.insert(
+            String::from("bytes"),
+            Hocon::Array(vec![
+                Hocon::Integer(0),
+                Hocon::Integer(104),
+                Hocon::Integer(255),
+            ]),
+        );
+        let res: super::Result<WithBytes> = super::from_hocon(Hocon::Hash(hm));
+        assert_eq!(res.expect("during test").bytes, Bytes(vec![0, 104, 255]));
+
+        let mut hm = LinkedHashMap::new();
+        hm.insert(String::from("bytes"), Hocon::String(String::from("hé")));
+        let res: super::Result<WithBytes> = super::from_hocon(Hocon::Hash(hm));
+        assert_eq!(
+            res.expect("during test").bytes,
+            Bytes(vec![104, 0xc3, 0xa9])
+        );
+
+        let mut hm = LinkedHashMap::new();
+        hm.insert(
+            String::from("bytes"),
+            Hocon::Array(vec![Hocon::Integer(256)]),
+        );
+        let res: super::Result<WithBytes> = super::from_hocon(Hocon::Hash(hm));
+        assert_eq!(
+            res.map(|_| ()).map_err(|err| err.message),
+            Err(String::from(
+                "bytes: Invalid type for field \"bytes\", expected bytes, found array"
+            ))
+        );
+    }
+
+    #[test]
+    fn can_deserialize_vec_u8_from_integer_array() {
+        #[derive(Deserialize, Debug)]
+        struct WithVec {
+            data: Vec<u8>,
+        }
+        let mut hm = LinkedHashMap::new();
+        hm.insert(
+            String::from("data"),
+            Hocon::Array(vec![Hocon::Integer(1), Hocon::Integer(2)]),
+        );
+        let res: super::Result<WithVec> = super::from_hocon(Hocon::Hash(hm));
+        assert_eq!(res.expect("during test").data, vec![1, 2]);
+    }
+
+    #[test]
+    fn will_fail_on_extra_field_when_denied() {
+        let mut hm = LinkedHashMap::new();
+        hm.insert(String::from("int"), Hocon::Integer(5));
+        hm.insert(String::from("float"), Hocon::Integer(6));
+        let doc = Hocon::Hash(hm.clone());
+
+        let res: super::Result<SimpleWithoutExtra> = dbg!(super::from_hocon(dbg!(doc)));
+        assert!(res.is_ok());
+
+        hm.insert(String::from("extra"), Hocon::Integer(10));
+        let doc = Hocon::Hash(hm);
+
+        let res: super::Result<SimpleWithoutExtra> = dbg!(super::from_hocon(dbg!(doc)));
+        assert_eq!(
+            res.map_err(|err| err.message).map(|_| ()),
+            Err(String::from(
+                "extra: unknown field `extra`, expected `int` or `float`"
+            ))
+        );
+    }
+
     #[test]
     fn will_fail_on_wrong_type() {
         let mut hm = LinkedHashMap::new();
@@ -1034,6 +1211,33 @@ mod tests {
         item: MyEnum,
     }
 
+    #[derive(Deserialize, Debug)]
+    struct WithUnit {
+        unit: (),
+    }
+
+    #[test]
+    fn deserialize_unit_from_null() {
+        let mut hm = LinkedHashMap::new();
+        hm.insert(String::from("unit"), Hocon::Null);
+        let doc = Hocon::Hash(hm);
+
+        let res: super::Result<WithUnit> = dbg!(super::from_hocon(dbg!(doc)));
+        assert!(res.is_ok());
+
+        let mut hm = LinkedHashMap::new();
+        hm.insert(String::from("unit"), Hocon::Integer(5));
+        let doc = Hocon::Hash(hm);
+
+        let res: super::Result<WithUnit> = dbg!(super::from_hocon(dbg!(doc)));
+        assert_eq!(
+            res.map(|_| ()).map_err(|err| err.message),
+            Err(String::from(
+                "unit: Invalid type for field \"unit\", expected null, found integer"
+            ))
+        );
+    }
+
     #[test]
     fn deserialize_unit_enum() {
         let mut hm = LinkedHashMap::new();
@@ -17,6 +17,19 @@ impl serde::de::Error for Error {
             message: format!("{}", msg),
         }
     }
+
+    fn invalid_type(unexp: serde::de::Unexpected, exp: &dyn serde::de::Expected) -> Error {
+        match unexp {
+            // values are owned by the deserializer and can't be lent to a `&str`, which is
+            // the only target expecting a borrowed string
+            serde::de::Unexpected::Str(_) if exp.to_string() == "a borrowed string" => Error {
+                message: String::from(
+                    "HOCON values can't be borrowed, use String or Cow<str> instead of &str",
+                ),
+            },
+            _ => Error::custom(format_args!("invalid type: {}, expected {}", unexp, exp)),
+        }
+    }
 }
 impl<E: std::fmt::Display> From<serde_path_to_error::Error<E>> for Error {
     fn from(msg: serde_path_to_error::Error<E>) -> Error {
@@ -46,4 +59,20 @@ mod tests {
         assert_eq!(error.to_string(), "my error");
         assert!(error.source().is_none());
     }
+
+    #[test]
+    fn borrowed_string_error() {
+        let error: Error =
+            Error::invalid_type(serde::de::Unexpected::Str("a"), &"a borrowed string");
+        assert_eq!(
+            error.to_string(),
+            "HOCON values can't be borrowed, use String or Cow<str> instead of &str"
+        );
+
+        let error: Error = Error::invalid_type(serde::de::Unexpected::Str("a"), &"an integer");
+        assert_eq!(
+            error.to_string(),
+            "invalid type: string \"a\", expected an integer"
+        );
+    }
 }
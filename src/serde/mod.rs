@@ -1,5 +1,7 @@
 pub mod de;
 
+mod ser;
+
 pub mod wrappers;
 
 pub(crate) mod error;
@@ -0,0 +1,63 @@
+//! Serialize a Hocon document with `serde`
+
+use serde::ser::{Error, Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use crate::Hocon;
+
+/// Serialize a `Hocon` value, mapping arrays to sequences and objects to maps. Serializing
+/// a [`BadValue`](enum.Hocon.html#variant.BadValue) fails with its error
+impl Serialize for Hocon {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Hocon::Integer(i) => serializer.serialize_i64(*i),
+            Hocon::Real(f) => serializer.serialize_f64(*f),
+            Hocon::String(s) => serializer.serialize_str(s),
+            Hocon::Boolean(b) => serializer.serialize_bool(*b),
+            Hocon::Null => serializer.serialize_unit(),
+            Hocon::Array(vec) => {
+                let mut seq = serializer.serialize_seq(Some(vec.len()))?;
+                for item in vec {
+                    seq.serialize_element(item)?;
+                }
+                seq.end()
+            }
+            Hocon::Hash(map) => {
+                let mut ser_map = serializer.serialize_map(Some(map.len()))?;
+                for (key, value) in map {
+                    ser_map.serialize_entry(key, value)?;
+                }
+                ser_map.end()
+            }
+            Hocon::BadValue(err) => Err(S::Error::custom(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Hocon, HoconLoader};
+
+    #[test]
+    fn can_serialize_to_json() {
+        let doc = HoconLoader::new()
+            .load_str(r#"{ a { b = [1, 2.5, "c", true, null] }, d = hello world }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        assert_eq!(
+            serde_json::to_string(&doc).expect("during test"),
+            r#"{"a":{"b":[1,2.5,"c",true,null]},"d":"hello world"}"#
+        );
+    }
+
+    #[test]
+    fn will_fail_on_bad_value() {
+        let doc = Hocon::Array(vec![Hocon::Integer(1), Hocon::BadValue(Error::MissingKey)]);
+
+        assert!(serde_json::to_string(&doc).is_err());
+    }
+}
@@ -7,7 +7,7 @@ use std::{
 };
 
 use serde::{
-    de::{self, Deserialize, Visitor},
+    de::{self, Deserialize, SeqAccess, Visitor},
     Deserializer,
 };
 
@@ -15,7 +15,8 @@ use crate::Hocon;
 
 /// Wrapper for custom deserialization from Hocon.
 ///
-/// Implemented for [`Duration`]
+/// Implemented for [`Duration`], for `Vec<Duration>` to read an array of durations, and for
+/// [`ByteSize`] to read a size in bytes like `"32.5M"`
 ///
 /// ## As a newtype wrapper
 ///
@@ -119,3 +120,104 @@ impl Serde<Duration> {
         deserializer.deserialize_str(StringDurationVisitor)
     }
 }
+
+struct DurationSeqVisitor;
+
+impl<'de> Visitor<'de> for DurationSeqVisitor {
+    type Value = Vec<Duration>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of durations")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut durations = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(Serde(duration)) = seq.next_element::<Serde<Duration>>()? {
+            durations.push(duration);
+        }
+        Ok(durations)
+    }
+}
+
+impl<'de> Deserialize<'de> for Serde<Vec<Duration>> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Serde(deserializer.deserialize_seq(DurationSeqVisitor)?))
+    }
+}
+
+impl Serde<Vec<Duration>> {
+    /// Custom deserializer for an array of durations, to use with Serde `deserialize_with`
+    /// attribute
+    pub fn with<'de, D>(deserializer: D) -> Result<Vec<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(DurationSeqVisitor)
+    }
+}
+
+/// A size in bytes, read from the
+/// [size in bytes format](https://github.com/lightbend/config/blob/master/HOCON.md#size-in-bytes-format)
+/// through [`Serde<ByteSize>`](struct.Serde.html)
+///
+/// ```rust
+/// # use hocon::de::wrappers::{ByteSize, Serde};
+/// # use serde::Deserialize;
+/// #[derive(Deserialize, Debug)]
+/// struct StructWithSize {
+///     #[serde(deserialize_with = "Serde::<ByteSize>::with")]
+///     cache_size: u64,
+/// }
+/// # fn usage() {
+/// # let doc = r#"{"cache_size":"1.5KiB"}"#;
+///
+/// let my_struct: StructWithSize = hocon::de::from_str(doc).unwrap();
+/// assert_eq!(my_struct.cache_size, 1536);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteSize(pub u64);
+
+struct StringByteSizeVisitor;
+
+impl<'de> Visitor<'de> for StringByteSizeVisitor {
+    type Value = ByteSize;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a size in bytes")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Hocon::str_as_bytes(v)
+            .map(ByteSize)
+            .ok_or_else(|| E::custom(format!("expected size in bytes, found \"{}\"", v)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Serde<ByteSize> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Serde(deserializer.deserialize_str(StringByteSizeVisitor)?))
+    }
+}
+
+impl Serde<ByteSize> {
+    /// Custom deserializer for a size in bytes, to use with Serde `deserialize_with` attribute
+    pub fn with<'de, D>(deserializer: D) -> Result<u64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(deserializer.deserialize_str(StringByteSizeVisitor)?.0)
+    }
+}
@@ -1,4 +1,6 @@
 use linked_hash_map::LinkedHashMap;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::ops::Index;
 
 /// An HOCON document
@@ -75,7 +77,7 @@ pub enum Hocon {
     Boolean(bool),
     /// An array of `Hocon` values
     Array(Vec<Hocon>),
-    /// An HashMap of `Hocon` values with keys
+    /// An HashMap of `Hocon` values with keys, in the order they were defined in the document
     Hash(LinkedHashMap<String, Hocon>),
     /// A null value
     Null,
@@ -101,34 +103,168 @@ impl Index<usize> for Hocon {
 
     fn index(&self, idx: usize) -> &Self::Output {
         match self {
-            Hocon::Array(vec) => vec.get(idx).unwrap_or(&NOT_FOUND),
-            Hocon::Hash(hash) => {
-                let mut keys_as_usize = hash
-                    .keys()
-                    .filter_map(|k| k.parse::<usize>().ok().map(|v| (k, v)))
-                    .collect::<Vec<_>>();
-                keys_as_usize.sort_by(|(_, v0), (_, v1)| v0.cmp(v1));
-                keys_as_usize
-                    .get(idx)
-                    .and_then(|(k, _)| hash.get(*k))
-                    .unwrap_or(&INVALID_KEY)
+            Hocon::Array(_) => self.get_index(idx).unwrap_or(&NOT_FOUND),
+            _ => self.get_index(idx).unwrap_or(&INVALID_KEY),
+        }
+    }
+}
+
+/// Conversion from an [`Hocon`](enum.Hocon.html) value to a basic type, using the same
+/// automatic type conversions as the `as_*` methods
+pub trait FromHocon: Sized {
+    /// Try to convert a value, returning `None` if it is not of the expected type
+    fn from_hocon(value: &Hocon) -> Option<Self>;
+}
+
+macro_rules! impl_from_hocon_for_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl FromHocon for $ty {
+                fn from_hocon(value: &Hocon) -> Option<Self> {
+                    value.as_i64().and_then(|v| <$ty>::try_from(v).ok())
+                }
             }
-            _ => &INVALID_KEY,
+        )*
+    };
+}
+impl_from_hocon_for_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl FromHocon for f64 {
+    fn from_hocon(value: &Hocon) -> Option<Self> {
+        value.as_f64()
+    }
+}
+
+impl FromHocon for f32 {
+    fn from_hocon(value: &Hocon) -> Option<Self> {
+        value.as_f64().map(|v| v as f32)
+    }
+}
+
+impl FromHocon for bool {
+    fn from_hocon(value: &Hocon) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+impl FromHocon for String {
+    fn from_hocon(value: &Hocon) -> Option<Self> {
+        value.as_string()
+    }
+}
+
+macro_rules! impl_from_for_hocon {
+    ($variant:ident: $($ty:ty),*) => {
+        $(
+            impl From<$ty> for Hocon {
+                fn from(value: $ty) -> Self {
+                    Hocon::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+impl_from_for_hocon!(Integer: i8, i16, i32, i64, u8, u16, u32);
+impl_from_for_hocon!(Real: f32, f64);
+impl_from_for_hocon!(Boolean: bool);
+impl_from_for_hocon!(String: String, &str);
+impl_from_for_hocon!(Array: Vec<Hocon>);
+
+impl TryFrom<Hocon> for Vec<String> {
+    type Error = crate::Error;
+
+    fn try_from(value: Hocon) -> Result<Self, Self::Error> {
+        let mismatch = || crate::Error::TypeMismatch {
+            expected: String::from("an array of strings"),
+        };
+        match value {
+            Hocon::Array(values) => values
+                .iter()
+                .map(|value| value.as_string().ok_or_else(mismatch))
+                .collect(),
+            _ => Err(mismatch()),
+        }
+    }
+}
+
+impl TryFrom<Hocon> for HashMap<String, String> {
+    type Error = crate::Error;
+
+    fn try_from(value: Hocon) -> Result<Self, Self::Error> {
+        let mismatch = || crate::Error::TypeMismatch {
+            expected: String::from("a hash of strings"),
+        };
+        match value {
+            Hocon::Hash(hash) => hash
+                .into_iter()
+                .map(|(key, value)| value.as_string().map(|v| (key, v)).ok_or_else(mismatch))
+                .collect(),
+            _ => Err(mismatch()),
         }
     }
 }
 
 impl Hocon {
-    /// Try to cast a value as a `f64` value
+    /// Try to cast a value as a `f64` value. Strings that are not finite numbers, like
+    /// `inf` or `nan`, are not numbers in HOCON and return `None`
     pub fn as_f64(&self) -> Option<f64> {
         match *self {
             Hocon::Real(ref v) => Some(*v),
             Hocon::Integer(ref v) => Some(*v as f64),
-            Hocon::String(ref v) => v.parse::<f64>().ok(),
+            Hocon::String(ref v) => v.parse::<f64>().ok().filter(|v| v.is_finite()),
             _ => None,
         }
     }
 
+    /// Try to cast a value as a `f32` value. Values that are out of range for a `f32`,
+    /// or are not finite, return `None`
+    pub fn as_f32(&self) -> Option<f32> {
+        self.as_f64().map(|v| v as f32).filter(|v| v.is_finite())
+    }
+
+    /// Try to cast a value as a `f64` value, like [`as_f64`](enum.Hocon.html#method.as_f64),
+    /// reading strings with `decimal` as the decimal separator, as in `"1,5"`. As `,` separates
+    /// values in HOCON, this is only useful for quoted strings. A string that also contains a
+    /// `.` is ambiguous and returns `None`
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ ratio = "1,5" }"#)?.hocon()?;
+    /// assert_eq!(doc["ratio"].as_f64_locale(','), Some(1.5));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_f64_locale(&self, decimal: char) -> Option<f64> {
+        match *self {
+            Hocon::String(ref v) if decimal != '.' && v.contains('.') => None,
+            Hocon::String(ref v) => Hocon::String(v.replace(decimal, ".")).as_f64(),
+            _ => self.as_f64(),
+        }
+    }
+
+    /// Try to cast a value as a `f64` value, also accepting booleans as `1.0` or `0.0`
+    /// for legacy documents using booleans where numbers are expected. This is more lenient
+    /// than [`as_f64`](enum.Hocon.html#method.as_f64), which rejects booleans
+    pub fn as_f64_from_bool(&self) -> Option<f64> {
+        match *self {
+            Hocon::Boolean(true) => Some(1.0),
+            Hocon::Boolean(false) => Some(0.0),
+            _ => self.as_f64(),
+        }
+    }
+
+    /// Try to cast a nullable value as a `f64` value, to distinguish a
+    /// [`Hocon::Null`](enum.Hocon.html#variant.Null) value from a value of the wrong type.
+    /// Returns `Some(None)` for a null value, `Some(Some(v))` for a value accepted by
+    /// [`as_f64`](enum.Hocon.html#method.as_f64), and `None` otherwise
+    pub fn as_f64_nullable(&self) -> Option<Option<f64>> {
+        match *self {
+            Hocon::Null => Some(None),
+            _ => self.as_f64().map(Some),
+        }
+    }
+
     /// Try to cast a value as a `i64` value
     pub fn as_i64(&self) -> Option<i64> {
         match *self {
@@ -138,444 +274,2470 @@ impl Hocon {
         }
     }
 
-    /// Try to cast a value as a `String` value
-    pub fn as_string(&self) -> Option<String> {
+    /// Try to cast a value as a `u64` value, returning `None` if it is out of range
+    pub fn as_u64(&self) -> Option<u64> {
         match *self {
-            Hocon::String(ref v) => Some(v.to_string()),
-            Hocon::Boolean(true) => Some("true".to_string()),
-            Hocon::Boolean(false) => Some("false".to_string()),
-            Hocon::Integer(i) => Some(i.to_string()),
-            Hocon::Real(f) => Some(f.to_string()),
+            Hocon::Integer(ref v) => u64::try_from(*v).ok(),
+            Hocon::String(ref v) => v.parse::<u64>().ok(),
             _ => None,
         }
     }
 
-    pub(crate) fn as_internal_string(&self) -> Option<String> {
+    /// Try to cast a value as a `i32` value, returning `None` if it is out of range
+    pub fn as_i32(&self) -> Option<i32> {
         match *self {
-            Hocon::String(ref v) => Some(v.to_string()),
-            Hocon::Boolean(true) => Some("true".to_string()),
-            Hocon::Boolean(false) => Some("false".to_string()),
-            Hocon::Integer(i) => Some(i.to_string()),
-            Hocon::Real(f) => Some(f.to_string()),
-            Hocon::Null => Some("null".to_string()),
+            Hocon::Integer(ref v) => i32::try_from(*v).ok(),
+            Hocon::String(ref v) => v.parse::<i32>().ok(),
             _ => None,
         }
     }
 
-    /// Try to cast a value as a `bool` value
-    pub fn as_bool(&self) -> Option<bool> {
+    /// Try to cast a value as a `u32` value, returning `None` if it is out of range
+    pub fn as_u32(&self) -> Option<u32> {
         match *self {
-            Hocon::Boolean(ref v) => Some(*v),
-            Hocon::String(ref v) if v == "yes" || v == "true" || v == "on" => Some(true),
-            Hocon::String(ref v) if v == "no" || v == "false" || v == "off" => Some(false),
+            Hocon::Integer(ref v) => u32::try_from(*v).ok(),
+            Hocon::String(ref v) => v.parse::<u32>().ok(),
             _ => None,
         }
     }
-}
-
-mod unit_format {
-    use nom::*;
-
-    named!(
-        parse_float<types::CompleteStr, f64>,
-        complete!(flat_map!(recognize_float, parse_to!(f64)))
-    );
 
-    pub(crate) fn value_and_unit(s: &str) -> Option<(f64, &str)> {
-        match parse_float(types::CompleteStr(s)) {
-            Ok((remaining, float)) => Some((float, &remaining)),
+    /// Try to cast a value as a `usize` value, returning `None` if it is out of range
+    pub fn as_usize(&self) -> Option<usize> {
+        match *self {
+            Hocon::Integer(ref v) => usize::try_from(*v).ok(),
+            Hocon::String(ref v) => v.parse::<usize>().ok(),
             _ => None,
         }
     }
-}
-
-macro_rules! units {
-    ( match $input:expr, $( $first_unit:expr, $( $unit:expr ),* => $scale:expr ),* ) => {
-        match $input {
-            $(
-                Some((value, $first_unit))
-                $(
-                    | Some((value, $unit))
-                )* => Some((value * $scale as f64) as u64),
-            )*
-            _ => None
-        }
-    };
-}
-
-macro_rules! units_f {
-    ( match $input:expr, $( $first_unit:expr, $( $unit:expr ),* => $scale:expr ),* ) => {
-        match $input {
-            $(
-                Some((value, $first_unit))
-                $(
-                    | Some((value, $unit))
-                )* => Some(value * $scale ),
-            )*
-            _ => None
-        }
-    };
-}
 
-impl Hocon {
-    /// Try to return a value as a size in bytes according to
-    /// [size in bytes format](https://github.com/lightbend/config/blob/master/HOCON.md#size-in-bytes-format).
-    ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
-    ///
-    /// # Example
+    /// Name of the type of a value, for diagnostics
     ///
     /// ```rust
-    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # use hocon::{HoconLoader, Error};
     /// # fn main() -> Result<(), Error> {
-    /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ size = 1.5KiB }"#)?.hocon()?["size"].as_bytes(),
-    ///     Some(1536)
-    /// );
+    /// let doc = HoconLoader::new().load_str(r#"{ a = 5, b = [] }"#)?.hocon()?;
+    /// assert_eq!(doc["a"].type_name(), "integer");
+    /// assert_eq!(doc["b"].type_name(), "array");
+    /// assert_eq!(doc["c"].type_name(), "bad value");
     /// # Ok(())
     /// # }
     /// ```
-    pub fn as_bytes(&self) -> Option<u64> {
-        match *self {
-            Hocon::Integer(ref i) => Some(*i as u64),
-            // Hocon::Real(ref f) => Some(*f),
-            Hocon::String(ref s) => units!(
-                match unit_format::value_and_unit(s).map(|(value, unit)| (value, unit.trim())),
-                 "", "B", "b", "byte", "bytes"                     => 1,
-                 "kB", "kilobyte", "kilobytes"                     => 10u64.pow(3),
-                 "MB", "megabyte", "megabytes"                     => 10u64.pow(6),
-                 "GB", "gigabyte", "gigabytes"                     => 10u64.pow(9),
-                 "TB", "terabyte", "terabytes"                     => 10u64.pow(12),
-                 "PB", "petabyte", "petabytes"                     => 10u64.pow(15),
-                 "EB", "exabyte", "exabytes"                       => 10u64.pow(18),
-                 "ZB", "zettabyte", "zettabytes"                   => 10u64.pow(21),
-                 "YB", "yottabyte", "yottabytes"                   => 10u64.pow(24),
-                 "K", "k", "Ki", "KiB", "kibibyte", "kibibytes"    => 2u64.pow(10),
-                 "M", "m", "Mi", "MiB", "mebibyte", "mebibytes"    => 2u64.pow(20),
-                 "G", "g", "Gi", "GiB", "gibibyte", "gibibytes"    => 2u64.pow(30),
-                 "T", "t", "Ti", "TiB", "tebibyte", "tebibytes"    => 2u64.pow(40),
-                 "P", "p", "Pi", "PiB", "pebibyte", "pebibytes"    => 2u64.pow(50),
-                 "E", "e", "Ei", "EiB", "exbibyte", "exbibytes"    => 2u64.pow(60),
-                 "Z", "z", "Zi", "ZiB", "zebibyte", "zebibytes"    => 2u64.pow(70),
-                 "Y", "y", "Yi", "YiB", "yobibyte", "yobibytes"    => 2u64.pow(80)
-            ),
-            _ => None,
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Hocon::Real(_) => "real",
+            Hocon::Integer(_) => "integer",
+            Hocon::String(_) => "string",
+            Hocon::Boolean(_) => "boolean",
+            Hocon::Array(_) => "array",
+            Hocon::Hash(_) => "hash",
+            Hocon::Null => "null",
+            Hocon::BadValue(_) => "bad value",
         }
     }
 
-    /// Try to return a value as a duration in milliseconds according to
-    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
-    ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
-    ///
-    /// # Example
+    /// Get the message of the error inside a [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue),
+    /// or `None` for any other value
     ///
     /// ```rust
-    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # use hocon::{HoconLoader, Error};
     /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ a = 5 }"#)?.hocon()?;
+    /// assert_eq!(doc["a"].bad_value_message(), None);
     /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
-    ///         .hocon()?["duration"].as_milliseconds(),
-    ///     Some(5400000.0)
+    ///     doc["b"].bad_value_message(),
+    ///     Some(String::from("Error getting a value because key is not present"))
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub fn as_milliseconds(&self) -> Option<f64> {
-        match *self {
-            Hocon::Integer(ref i) => Some(*i as f64),
-            Hocon::Real(ref f) => Some(*f),
-            Hocon::String(ref s) => Self::str_as_milliseconds(s),
+    pub fn bad_value_message(&self) -> Option<String> {
+        match self {
+            Hocon::BadValue(err) => Some(err.to_string()),
             _ => None,
         }
     }
 
-    pub(crate) fn str_as_milliseconds(s: &str) -> Option<f64> {
-        units_f!(
-            match unit_format::value_and_unit(s).map(|(value, unit)| (value, unit.trim())),
-            "ns", "nano", "nanos", "nanosecond", "nanoseconds"          => 10.0f64.powf(-6.0),
-            "us", "micro", "micros", "microsecond", "microseconds"      => 10.0f64.powf(-3.0),
-            "", "ms", "milli", "millis", "millisecond", "milliseconds"  => 1.0,
-            "s", "second", "seconds"                                    => 1_000.0,
-            "m", "minute", "minutes"                                    => 1_000.0 * 60.0,
-            "h", "hour", "hours"                                        => 1_000.0 * 60.0 * 60.0,
-            "d", "day", "days"                                          => 1_000.0 * 60.0 * 60.0 * 24.0,
-            "w", "week", "weeks"                                        => 1_000.0 * 60.0 * 60.0 * 24.0 * 7.0,
-            "mo", "month", "months"                                     => 1_000.0 * 60.0 * 60.0 * 24.0 * 30.0,
-            "y", "year", "years"                                        => 1_000.0 * 60.0 * 60.0 * 24.0 * 365.0
-        )
-    }
-
-    /// Try to return a value as a duration in nanoseconds according to
-    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
-    ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
-    ///
-    /// # Example
+    /// Check if a value is a string that is empty or only contains whitespace. Such a string
+    /// can't be cast as a number either, but this is useful to treat it differently from a
+    /// malformed value
     ///
     /// ```rust
-    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # use hocon::{HoconLoader, Error};
     /// # fn main() -> Result<(), Error> {
-    /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
-    ///         .hocon()?["duration"].as_nanoseconds(),
-    ///     Some(5400000000000.0)
-    /// );
+    /// let doc = HoconLoader::new().load_str(r#"{ a = "  ", b = "1.5x" }"#)?.hocon()?;
+    /// assert!(doc["a"].is_blank());
+    /// assert!(!doc["b"].is_blank());
     /// # Ok(())
     /// # }
     /// ```
-    pub fn as_nanoseconds(&self) -> Option<f64> {
-        self.as_milliseconds().map(|v| v * 10.0f64.powf(6.0))
+    pub fn is_blank(&self) -> bool {
+        match self {
+            Hocon::String(s) => s.trim().is_empty(),
+            _ => false,
+        }
     }
 
-    /// Try to return a value as a duration in microseconds according to
-    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
-    ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
-    ///
-    /// # Example
+    /// Try to cast a value as a `String` value
+    pub fn as_string(&self) -> Option<String> {
+        match *self {
+            Hocon::String(ref v) => Some(v.to_string()),
+            Hocon::Boolean(true) => Some("true".to_string()),
+            Hocon::Boolean(false) => Some("false".to_string()),
+            Hocon::Integer(i) => Some(i.to_string()),
+            Hocon::Real(f) => Some(f.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Borrow a string value as a `&str`, without allocating. Unlike
+    /// [`as_string`](enum.Hocon.html#method.as_string), other values are not converted to a
+    /// string and return `None`
     ///
     /// ```rust
-    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # use hocon::{HoconLoader, Error};
     /// # fn main() -> Result<(), Error> {
-    /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
-    ///         .hocon()?["duration"].as_microseconds(),
-    ///     Some(5400000000.0)
-    /// );
+    /// let doc = HoconLoader::new().load_str(r#"{ host = localhost, port = 80 }"#)?.hocon()?;
+    /// assert_eq!(doc["host"].as_str(), Some("localhost"));
+    /// assert_eq!(doc["port"].as_str(), None);
+    /// assert_eq!(doc["port"].as_string(), Some(String::from("80")));
     /// # Ok(())
     /// # }
     /// ```
-    pub fn as_microseconds(&self) -> Option<f64> {
-        self.as_milliseconds().map(|v| v * 10.0f64.powf(3.0))
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Hocon::String(v) => Some(v.as_str()),
+            _ => None,
+        }
     }
 
-    /// Try to return a value as a duration in seconds according to
-    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
-    ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
-    ///
-    /// # Example
+    /// Check if a string value matches a glob `pattern`, where `*` matches any sequence of
+    /// characters and `?` matches exactly one character. Other values never match
     ///
     /// ```rust
-    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # use hocon::{HoconLoader, Error};
     /// # fn main() -> Result<(), Error> {
-    /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
-    ///         .hocon()?["duration"].as_seconds(),
-    ///     Some(5400.0)
-    /// );
+    /// let doc = HoconLoader::new().load_str(r#"{ route = "/api/v1/users" }"#)?.hocon()?;
+    /// assert!(doc["route"].string_matches("/api/v?/*"));
+    /// assert!(!doc["route"].string_matches("/admin/*"));
     /// # Ok(())
     /// # }
     /// ```
-    pub fn as_seconds(&self) -> Option<f64> {
-        self.as_milliseconds().map(|v| v * 10.0f64.powf(-3.0))
+    pub fn string_matches(&self, pattern: &str) -> bool {
+        let value = match self {
+            Hocon::String(v) => v.chars().collect::<Vec<_>>(),
+            _ => return false,
+        };
+        let pattern = pattern.chars().collect::<Vec<_>>();
+        let (mut v, mut p) = (0, 0);
+        // positions in the pattern after the last `*`, and in the value where it started matching
+        let mut last_star = None;
+        while v < value.len() {
+            match pattern.get(p).copied() {
+                Some('*') => {
+                    last_star = Some((p + 1, v));
+                    p += 1;
+                }
+                Some(c) if c == '?' || c == value[v] => {
+                    v += 1;
+                    p += 1;
+                }
+                _ => match last_star {
+                    // let the last `*` match one more character
+                    Some((star_p, star_v)) => {
+                        last_star = Some((star_p, star_v + 1));
+                        p = star_p;
+                        v = star_v + 1;
+                    }
+                    None => return false,
+                },
+            }
+        }
+        pattern[p..].iter().all(|c| *c == '*')
     }
 
-    /// Try to return a value as a duration in minutes according to
-    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
-    ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
+    /// Try to cast a value as a `String` value, like [`as_string`](enum.Hocon.html#method.as_string),
+    /// making sure reals are written in fixed notation and never in scientific notation.
+    /// Non finite reals return `None`.
     ///
     /// # Example
     ///
     /// ```rust
     /// # use hocon::{Hocon, HoconLoader, Error};
     /// # fn main() -> Result<(), Error> {
-    /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
-    ///         .hocon()?["duration"].as_minutes(),
-    ///     Some(90.0)
-    /// );
+    /// let doc = HoconLoader::new().load_str(r#"{ big = 1e20, small = 1e-7 }"#)?.hocon()?;
+    /// assert_eq!(doc["big"].as_plain_string(), Some(String::from("100000000000000000000")));
+    /// assert_eq!(doc["small"].as_plain_string(), Some(String::from("0.0000001")));
     /// # Ok(())
     /// # }
     /// ```
-    pub fn as_minutes(&self) -> Option<f64> {
-        self.as_milliseconds()
-            .map(|v| v * 10.0f64.powf(-3.0) / 60.0)
+    pub fn as_plain_string(&self) -> Option<String> {
+        match *self {
+            // `Display` for `f64` writes all the digits and never switches to an exponent
+            Hocon::Real(f) if f.is_finite() => Some(format!("{}", f)),
+            Hocon::Real(_) => None,
+            _ => self.as_string(),
+        }
     }
 
-    /// Try to return a value as a duration in hours according to
-    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
-    ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
+    /// Try to cast a value as a `String` value, like [`as_string`](enum.Hocon.html#method.as_string),
+    /// replacing the `${name}` markers left in the string with the value of `name` from `vars`.
+    /// Markers are only found in quoted strings, as they are not substituted when loading the
+    /// document. Markers for a name absent from `vars` are kept as they are.
     ///
     /// # Example
     ///
     /// ```rust
+    /// # use std::collections::HashMap;
     /// # use hocon::{Hocon, HoconLoader, Error};
     /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ url = "https://${host}/${path}" }"#)?.hocon()?;
+    /// let mut vars = HashMap::new();
+    /// vars.insert(String::from("host"), String::from("example.com"));
     /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
-    ///         .hocon()?["duration"].as_hours(),
-    ///     Some(1.5)
+    ///     doc["url"].as_string_interpolated(&vars),
+    ///     Some(String::from("https://example.com/${path}"))
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub fn as_hours(&self) -> Option<f64> {
-        self.as_milliseconds()
-            .map(|v| v * 10.0f64.powf(-3.0) / 60.0 / 60.0)
+    pub fn as_string_interpolated(&self, vars: &HashMap<String, String>) -> Option<String> {
+        let mut remaining = self.as_string()?;
+        let mut interpolated = String::with_capacity(remaining.len());
+        while let Some(start) = remaining.find("${") {
+            let end = match remaining[start..].find('}') {
+                Some(end) => start + end,
+                None => break,
+            };
+            interpolated.push_str(&remaining[..start]);
+            match vars.get(remaining[start + 2..end].trim()) {
+                Some(value) => interpolated.push_str(value),
+                None => interpolated.push_str(&remaining[start..=end]),
+            }
+            remaining = remaining.split_off(end + 1);
+        }
+        interpolated.push_str(&remaining);
+        Some(interpolated)
     }
 
-    /// Try to return a value as a duration in days according to
-    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    pub(crate) fn as_internal_string(&self) -> Option<String> {
+        match *self {
+            Hocon::String(ref v) => Some(v.to_string()),
+            Hocon::Boolean(true) => Some("true".to_string()),
+            Hocon::Boolean(false) => Some("false".to_string()),
+            Hocon::Integer(i) => Some(i.to_string()),
+            Hocon::Real(f) => Some(f.to_string()),
+            Hocon::Null => Some("null".to_string()),
+            _ => None,
+        }
+    }
+
+    /// Try to cast a value as a `bool` value
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Hocon::Boolean(ref v) => Some(*v),
+            Hocon::String(ref v) if v == "yes" || v == "true" || v == "on" => Some(true),
+            Hocon::String(ref v) if v == "no" || v == "false" || v == "off" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Try to cast a value as a `bool` value, like [`as_bool`](enum.Hocon.html#method.as_bool),
+    /// also reading the strings in `truthy` as `true` and the strings in `falsy` as `false`
     ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ cache = enabled, debug = off }"#)?.hocon()?;
+    /// assert_eq!(doc["cache"].as_bool_extended(&["enabled"], &["disabled"]), Some(true));
+    /// assert_eq!(doc["debug"].as_bool_extended(&["enabled"], &["disabled"]), Some(false));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_bool_extended(&self, truthy: &[&str], falsy: &[&str]) -> Option<bool> {
+        match *self {
+            Hocon::String(ref v) if truthy.contains(&v.as_str()) => Some(true),
+            Hocon::String(ref v) if falsy.contains(&v.as_str()) => Some(false),
+            _ => self.as_bool(),
+        }
+    }
+
+    /// Try to cast a value as an array of exactly `N` elements, for values with a fixed
+    /// size such as a RGB color. Returns `None` if the value is not an array or if it has a
+    /// different number of elements
     ///
-    /// # Example
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error, Hocon};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ color = [255, 128, 0] }"#)?.hocon()?;
+    /// let [r, g, b] = doc["color"].as_fixed_array::<3>().expect("a RGB color");
+    /// assert_eq!((r.as_i64(), g.as_i64(), b.as_i64()), (Some(255), Some(128), Some(0)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_fixed_array<const N: usize>(&self) -> Option<[Hocon; N]> {
+        match self {
+            Hocon::Array(vec) if vec.len() == N => <[Hocon; N]>::try_from(vec.clone()).ok(),
+            _ => None,
+        }
+    }
+
+    /// Try to read an array of `[key, value]` pairs, used to keep the order of a mapping.
+    /// Returns `None` if the value is not an array, if an element is not an array of two
+    /// elements, or if a key can't be read as a `String`
     ///
     /// ```rust
-    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # use hocon::{HoconLoader, Error, Hocon};
     /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ headers = [[accept, json], [retry, 3]] }"#)?.hocon()?;
     /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
-    ///         .hocon()?["duration"].as_days(),
-    ///     Some(0.0625)
+    ///     doc["headers"].as_kv_pairs(),
+    ///     Some(vec![
+    ///         (String::from("accept"), &Hocon::String(String::from("json"))),
+    ///         (String::from("retry"), &Hocon::Integer(3)),
+    ///     ])
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub fn as_days(&self) -> Option<f64> {
-        self.as_milliseconds()
-            .map(|v| v * 10.0f64.powf(-3.0) / 60.0 / 60.0 / 24.0)
+    pub fn as_kv_pairs(&self) -> Option<Vec<(String, &Hocon)>> {
+        match self {
+            Hocon::Array(vec) => vec
+                .iter()
+                .map(|pair| match pair {
+                    Hocon::Array(pair) if pair.len() == 2 => {
+                        pair[0].as_string().map(|key| (key, &pair[1]))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
     }
 
-    /// Try to return a value as a duration in weeks according to
-    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    /// Try to read a value as an amount of money, with an optional leading currency symbol
+    /// (`$1.50`) or trailing currency code (`1.50 USD`). Returns the amount and the
+    /// currency, if any
     ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ price = "1.50 USD" }"#)?.hocon()?["price"].as_money(),
+    ///     Some((1.5, Some(String::from("USD"))))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_money(&self) -> Option<(f64, Option<String>)> {
+        fn is_number_char(c: char) -> bool {
+            c.is_ascii_digit() || c == '.' || c == '-' || c == '+'
+        }
+
+        match self {
+            Hocon::Integer(_) | Hocon::Real(_) => self.as_f64().map(|amount| (amount, None)),
+            Hocon::String(s) => {
+                let s = s.trim();
+                let (symbol, rest) = s.split_at(s.find(is_number_char)?);
+                let (number, code) =
+                    rest.split_at(rest.find(|c| !is_number_char(c)).unwrap_or(rest.len()));
+                let amount = number.parse::<f64>().ok()?;
+                match (symbol.trim(), code.trim()) {
+                    ("", "") => Some((amount, None)),
+                    (symbol, "") => Some((amount, Some(String::from(symbol)))),
+                    ("", code) if code.chars().all(char::is_alphabetic) => {
+                        Some((amount, Some(String::from(code))))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Try to find the position of a string value in a list of `allowed` values. The
+    /// comparison is case sensitive, and `None` is returned if the value is not in the list
     ///
-    /// # Example
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ level = warn }"#)?.hocon()?["level"]
+    ///         .as_enum_index(&["debug", "info", "warn", "error"]),
+    ///     Some(2)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_enum_index(&self, allowed: &[&str]) -> Option<usize> {
+        match self {
+            Hocon::String(s) => allowed.iter().position(|value| value == s),
+            _ => None,
+        }
+    }
+
+    /// Try to cast a value as a `Vec<String>`, flattening nested arrays. Every leaf must
+    /// be a [`Hocon::String`](enum.Hocon.html#variant.String), `None` is returned otherwise
+    /// or if the value is not an array
     ///
     /// ```rust
-    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # use hocon::{HoconLoader, Error};
     /// # fn main() -> Result<(), Error> {
     /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 days  }"#)?
-    ///         .hocon()?["duration"].as_weeks(),
-    ///     Some(0.21428571428571427)
+    ///     HoconLoader::new().load_str(r#"{ groups = [[a, b], [c]] }"#)?.hocon()?["groups"]
+    ///         .as_flat_string_vec(),
+    ///     Some(vec![String::from("a"), String::from("b"), String::from("c")])
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub fn as_weeks(&self) -> Option<f64> {
-        self.as_milliseconds()
-            .map(|v| v * 10.0f64.powf(-3.0) / 60.0 / 60.0 / 24.0 / 7.0)
+    pub fn as_flat_string_vec(&self) -> Option<Vec<String>> {
+        fn flatten_into(value: &Hocon, strings: &mut Vec<String>) -> Option<()> {
+            match value {
+                Hocon::Array(values) => values
+                    .iter()
+                    .try_for_each(|value| flatten_into(value, strings)),
+                Hocon::String(s) => {
+                    strings.push(s.clone());
+                    Some(())
+                }
+                _ => None,
+            }
+        }
+
+        match self {
+            Hocon::Array(_) => {
+                let mut strings = vec![];
+                flatten_into(self, &mut strings).map(|_| strings)
+            }
+            _ => None,
+        }
     }
 
-    /// Try to return a value as a duration in months according to
-    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    /// Join the elements of an array with `sep`, casting each of them as a `String` like
+    /// [`as_string`](enum.Hocon.html#method.as_string). `None` is returned if the value is
+    /// not an array or if an element can't be cast as a `String`
     ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ path = [usr, local, bin] }"#)?.hocon()?["path"]
+    ///         .join_strings("/"),
+    ///     Some(String::from("usr/local/bin"))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn join_strings(&self, sep: &str) -> Option<String> {
+        match self {
+            Hocon::Array(values) => values
+                .iter()
+                .map(Hocon::as_string)
+                .collect::<Option<Vec<_>>>()
+                .map(|strings| strings.join(sep)),
+            _ => None,
+        }
+    }
+
+    /// Try to return an array of single character strings as a `Vec<char>`. `None` is
+    /// returned if the value is not an array or if an element is not a string of exactly
+    /// one character
     ///
-    /// # Example
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ delimiters = [",", ";"] }"#)?.hocon()?["delimiters"]
+    ///         .as_char_array(),
+    ///     Some(vec![',', ';'])
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_char_array(&self) -> Option<Vec<char>> {
+        match self {
+            Hocon::Array(values) => values
+                .iter()
+                .map(|value| match value {
+                    Hocon::String(s) => {
+                        let mut chars = s.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(c), None) => Some(c),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    /// Return the elements of an array, treating any other value as an array of one element,
+    /// so that `tags = foo` can be read like `tags = [foo]`. `null` and missing values are
+    /// read as an empty array
     ///
     /// ```rust
     /// # use hocon::{Hocon, HoconLoader, Error};
     /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ a = [foo, bar], b = foo }"#)?.hocon()?;
+    /// assert_eq!(doc["a"].as_array_coerced().len(), 2);
     /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 days  }"#)?
-    ///         .hocon()?["duration"].as_months(),
-    ///     Some(0.05)
+    ///     doc["b"].as_array_coerced(),
+    ///     vec![&Hocon::String(String::from("foo"))]
     /// );
+    /// assert!(doc["c"].as_array_coerced().is_empty());
     /// # Ok(())
     /// # }
     /// ```
-    pub fn as_months(&self) -> Option<f64> {
-        self.as_milliseconds()
-            .map(|v| v * 10.0f64.powf(-3.0) / 60.0 / 60.0 / 24.0 / 30.0)
+    pub fn as_array_coerced(&self) -> Vec<&Hocon> {
+        match self {
+            Hocon::Array(values) => values.iter().collect(),
+            Hocon::Null | Hocon::BadValue(_) => vec![],
+            value => vec![value],
+        }
+    }
+
+    /// Try to read a string as a semantic version `major.minor.patch`. A pre-release or
+    /// build metadata, as in `1.2.3-beta+001`, is ignored. All three numbers are required,
+    /// `None` is returned for `1.2` as well as for strings that are not versions
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ version = 1.12.3 }"#)?.hocon()?["version"]
+    ///         .as_version(),
+    ///     Some((1, 12, 3))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_version(&self) -> Option<(u64, u64, u64)> {
+        match self {
+            Hocon::String(s) => {
+                let core = s.split(['-', '+']).next()?;
+                let mut numbers = core.split('.').map(|number| {
+                    if !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()) {
+                        number.parse::<u64>().ok()
+                    } else {
+                        None
+                    }
+                });
+                match (
+                    numbers.next(),
+                    numbers.next(),
+                    numbers.next(),
+                    numbers.next(),
+                ) {
+                    (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) => {
+                        Some((major, minor, patch))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Try to read a string as a time of day `HH:MM` or `HH:MM:SS`, returning the number of
+    /// seconds since midnight. Each component must have two digits, and `None` is returned if
+    /// one of them is out of range, as in `25:00`
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ start = "14:30" }"#)?.hocon()?["start"]
+    ///         .as_time_of_day_seconds(),
+    ///     Some(52200)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_time_of_day_seconds(&self) -> Option<u32> {
+        match self {
+            Hocon::String(s) => {
+                let mut components = s.split(':').map(|component| {
+                    if component.len() == 2 && component.bytes().all(|b| b.is_ascii_digit()) {
+                        component.parse::<u32>().ok()
+                    } else {
+                        None
+                    }
+                });
+                let (hours, minutes, seconds) = match (
+                    components.next(),
+                    components.next(),
+                    components.next(),
+                    components.next(),
+                ) {
+                    (Some(Some(hours)), Some(Some(minutes)), None, None) => (hours, minutes, 0),
+                    (Some(Some(hours)), Some(Some(minutes)), Some(Some(seconds)), None) => {
+                        (hours, minutes, seconds)
+                    }
+                    _ => return None,
+                };
+                if hours < 24 && minutes < 60 && seconds < 60 {
+                    Some(hours * 3600 + minutes * 60 + seconds)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Compare two values, ignoring differences that don't change the value: an
+    /// [`Hocon::Integer`](enum.Hocon.html#variant.Integer) and a
+    /// [`Hocon::Real`](enum.Hocon.html#variant.Real) with the same numeric value are equal,
+    /// and the order of keys in an [`Hocon::Hash`](enum.Hocon.html#variant.Hash) is ignored
+    pub fn value_eq(&self, other: &Hocon) -> bool {
+        match (self, other) {
+            (Hocon::Integer(i), Hocon::Real(f)) | (Hocon::Real(f), Hocon::Integer(i)) => {
+                (*i as f64 - *f).abs() < f64::EPSILON
+            }
+            (Hocon::Array(values), Hocon::Array(other_values)) => {
+                values.len() == other_values.len()
+                    && values
+                        .iter()
+                        .zip(other_values)
+                        .all(|(value, other_value)| value.value_eq(other_value))
+            }
+            (Hocon::Hash(hash), Hocon::Hash(other_hash)) => {
+                hash.len() == other_hash.len()
+                    && hash.iter().all(|(key, value)| {
+                        other_hash
+                            .get(key)
+                            .map(|other_value| value.value_eq(other_value))
+                            .unwrap_or(false)
+                    })
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Remove duplicate elements from an array, keeping the first occurrence of each
+    /// element. Elements are compared with [`value_eq`](enum.Hocon.html#method.value_eq).
+    /// This does nothing if the value is not an array
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error, Hocon};
+    /// # fn main() -> Result<(), Error> {
+    /// let mut tags = HoconLoader::new().load_str(r#"{ tags = [b, a, b] }"#)?.hocon()?["tags"].clone();
+    /// tags.dedup();
+    /// assert_eq!(
+    ///     tags,
+    ///     Hocon::Array(vec![Hocon::String(String::from("b")), Hocon::String(String::from("a"))])
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dedup(&mut self) {
+        if let Hocon::Array(values) = self {
+            let mut deduped: Vec<Hocon> = Vec::with_capacity(values.len());
+            for value in values.drain(..) {
+                if !deduped.iter().any(|kept| kept.value_eq(&value)) {
+                    deduped.push(value);
+                }
+            }
+            *values = deduped;
+        }
+    }
+
+    /// Rename a key of an [`Hocon::Hash`](enum.Hocon.html#variant.Hash), keeping its value
+    /// and its position. Returns `false` without changing anything if `from` is not present,
+    /// if `to` is already present, or if the value is not an
+    /// [`Hocon::Hash`](enum.Hocon.html#variant.Hash)
+    pub fn rename_key(&mut self, from: &str, to: &str) -> bool {
+        match self {
+            Hocon::Hash(hash) if hash.contains_key(from) && !hash.contains_key(to) => {
+                *hash = std::mem::take(hash)
+                    .into_iter()
+                    .map(|(key, value)| {
+                        if key == from {
+                            (String::from(to), value)
+                        } else {
+                            (key, value)
+                        }
+                    })
+                    .collect();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the value at `key` in an object. `None` is returned if the key is missing or if
+    /// the value is not an object
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error, Hocon};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ a = 5 }"#)?.hocon()?;
+    /// assert_eq!(doc.get("a").and_then(Hocon::as_i64), Some(5));
+    /// assert_eq!(doc.get("b"), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&Hocon> {
+        match self {
+            Hocon::Hash(hash) => hash.get(key),
+            _ => None,
+        }
+    }
+
+    /// Get the value at index `idx` in an array. As with indexing, an object with keys that
+    /// are integers can be accessed as an array, ordered by its keys. `None` is returned if
+    /// the index is out of bounds or if the value is not an array
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error, Hocon};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ a = [1, 2] }"#)?.hocon()?;
+    /// assert_eq!(doc["a"].get_index(1).and_then(Hocon::as_i64), Some(2));
+    /// assert_eq!(doc["a"].get_index(2), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_index(&self, idx: usize) -> Option<&Hocon> {
+        match self {
+            Hocon::Array(vec) => vec.get(idx),
+            Hocon::Hash(hash) => {
+                let mut keys_as_usize = hash
+                    .keys()
+                    .filter_map(|k| k.parse::<usize>().ok().map(|v| (k, v)))
+                    .collect::<Vec<_>>();
+                keys_as_usize.sort_by(|(_, v0), (_, v1)| v0.cmp(v1));
+                keys_as_usize.get(idx).and_then(|(k, _)| hash.get(*k))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the value at a path with keys separated by `.`, as with chained indexing. A key
+    /// that is a number is used as an index in an array. Keys containing a `.` can't be
+    /// reached this way, use indexing instead. The first missing key is returned as a
+    /// [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue), like with indexing
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error, Hocon};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ a { b = [{ c = 1 }] } }"#)?.hocon()?;
+    /// assert_eq!(doc.at_path("a.b.0.c"), &Hocon::Integer(1));
+    /// assert_eq!(doc.at_path("a.d.c"), &Hocon::BadValue(Error::MissingKey));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn at_path(&self, path: &str) -> &Hocon {
+        let mut current = self;
+        for key in path.split('.') {
+            current = match (current, key.parse::<usize>()) {
+                (Hocon::BadValue(_), _) => return current,
+                (Hocon::Array(_), Ok(idx)) => &current[idx],
+                _ => &current[key],
+            };
+        }
+        current
+    }
+
+    /// Check if there is a value at a path with keys separated by `.`, as with
+    /// [`at_path`](enum.Hocon.html#method.at_path). A `null` value is present
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ features { beta = null, gamma = true } }"#)?.hocon()?;
+    /// assert!(doc.contains_path("features.beta"));
+    /// assert!(!doc.contains_path("features.alpha"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn contains_path(&self, path: &str) -> bool {
+        !matches!(self.at_path(path), Hocon::BadValue(_))
+    }
+
+    /// Number of elements of an array or keys of an object. `None` is returned for other values
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ plugins {}, hosts = [a, b] }"#)?.hocon()?;
+    /// assert_eq!(doc["plugins"].len(), Some(0));
+    /// assert_eq!(doc["hosts"].len(), Some(2));
+    /// assert_eq!(doc["missing"].len(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            Hocon::Array(vec) => Some(vec.len()),
+            Hocon::Hash(hash) => Some(hash.len()),
+            _ => None,
+        }
+    }
+
+    /// Check if an array or an object is empty. `None` is returned for other values, so that
+    /// an empty object can be told apart from a missing one
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Iterate over the keys and values of an object, in the order of the keys. The iterator
+    /// is empty if the value is not an object
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new()
+    ///     .load_str(r#"{ servers { alpha.port = 80, beta.port = 8080 } }"#)?
+    ///     .hocon()?;
+    /// for (name, server) in doc["servers"].entries() {
+    ///     println!("{} listens on {:?}", name, server["port"].as_i64());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &Hocon)> {
+        match self {
+            Hocon::Hash(hash) => Some(hash.iter()),
+            _ => None,
+        }
+        .into_iter()
+        .flatten()
+    }
+
+    /// Iterate over the elements of an array. The iterator is empty if the value is not an
+    /// array
+    pub fn elements(&self) -> impl Iterator<Item = &Hocon> {
+        match self {
+            Hocon::Array(vec) => Some(vec.iter()),
+            _ => None,
+        }
+        .into_iter()
+        .flatten()
+    }
+
+    /// Get the first element of an array. `None` is returned if the array is empty or if the
+    /// value is not an array
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error, Hocon};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ a = [1, 2, 3] }"#)?.hocon()?;
+    /// assert_eq!(doc["a"].first(), Some(&Hocon::Integer(1)));
+    /// assert_eq!(doc["a"].last(), Some(&Hocon::Integer(3)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn first(&self) -> Option<&Hocon> {
+        match self {
+            Hocon::Array(vec) => vec.first(),
+            _ => None,
+        }
+    }
+
+    /// Get the last element of an array. `None` is returned if the array is empty or if the
+    /// value is not an array
+    pub fn last(&self) -> Option<&Hocon> {
+        match self {
+            Hocon::Array(vec) => vec.last(),
+            _ => None,
+        }
+    }
+
+    /// Get the array at `key` and convert each of its elements to `T`. `None` is returned
+    /// if the value is not an array or if any element can't be converted
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ ports = [80, 443] }"#)?.hocon()?
+    ///         .get_array_of::<u16>("ports"),
+    ///     Some(vec![80, 443])
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_array_of<T: FromHocon>(&self, key: &str) -> Option<Vec<T>> {
+        match &self[key] {
+            Hocon::Array(values) => values.iter().map(T::from_hocon).collect(),
+            _ => None,
+        }
+    }
+
+    /// Merge documents together, in order. As for duplicate keys in a document, objects
+    /// are merged with the previous ones and other values replace the previous value, so
+    /// later documents win
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let defaults = HoconLoader::new().load_str(r#"{ db { host: localhost, port: 5432 } }"#)?.hocon()?;
+    /// let overrides = HoconLoader::new().load_str(r#"{ db { port: 6543 } }"#)?.hocon()?;
+    /// let doc = Hocon::merge_all(vec![defaults, overrides]);
+    /// assert_eq!(doc["db"]["host"].as_string(), Some(String::from("localhost")));
+    /// assert_eq!(doc["db"]["port"].as_i64(), Some(6543));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge_all<I: IntoIterator<Item = Hocon>>(docs: I) -> Hocon {
+        docs.into_iter()
+            .reduce(|doc, other| doc.merged_with(other, false))
+            .unwrap_or_else(|| Hocon::Hash(LinkedHashMap::new()))
+    }
+
+    /// Deep merge `other` into this document. Objects are merged recursively, arrays and
+    /// other values from `other` replace the ones in this document, and a `null` in `other`
+    /// removes the key from the merged object
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let defaults = HoconLoader::new()
+    ///     .load_str(r#"{ db { host: localhost, port: 5432, replicas: [a, b] } }"#)?
+    ///     .hocon()?;
+    /// let overrides = HoconLoader::new()
+    ///     .load_str(r#"{ db { host: null, replicas: [c] } }"#)?
+    ///     .hocon()?;
+    /// let doc = defaults.merge(overrides);
+    /// assert_eq!(doc["db"]["host"], Hocon::BadValue(Error::MissingKey));
+    /// assert_eq!(doc["db"]["port"].as_i64(), Some(5432));
+    /// assert_eq!(doc["db"]["replicas"][0].as_string(), Some(String::from("c")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge(self, other: Hocon) -> Hocon {
+        self.merged_with(other, true)
+    }
+
+    // Merge `other` into this value, objects being merged recursively. When `null_removes`
+    // is set, a `Null` in `other` removes the key instead of replacing its value
+    fn merged_with(self, other: Hocon, null_removes: bool) -> Hocon {
+        match (self, other) {
+            (Hocon::Hash(mut hash), Hocon::Hash(other)) => {
+                for (key, value) in other {
+                    if null_removes && value == Hocon::Null {
+                        hash.remove(&key);
+                        continue;
+                    }
+                    match hash.get_mut(&key) {
+                        Some(existing) => {
+                            let previous = std::mem::replace(existing, Hocon::Null);
+                            *existing = previous.merged_with(value, null_removes);
+                        }
+                        None => {
+                            hash.insert(key, value);
+                        }
+                    }
+                }
+                Hocon::Hash(hash)
+            }
+            (_, other) => other,
+        }
+    }
+
+    /// Compare two values, ignoring the [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue)
+    /// keys of objects and elements of arrays on both sides, as if they were absent. This
+    /// is useful to compare documents loaded without strict mode
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let loaded = HoconLoader::new()
+    ///     .no_system()
+    ///     .load_str(r#"{ a: 1, b: ${missing} }"#)?
+    ///     .hocon()?;
+    /// let expected = HoconLoader::new().load_str(r#"{ a: 1 }"#)?.hocon()?;
+    /// assert_ne!(loaded, expected);
+    /// assert!(loaded.eq_treating_bad_as_absent(&expected));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn eq_treating_bad_as_absent(&self, other: &Hocon) -> bool {
+        fn is_present(value: &Hocon) -> bool {
+            !matches!(value, Hocon::BadValue(_))
+        }
+        match (self, other) {
+            (Hocon::Hash(left), Hocon::Hash(right)) => {
+                let left = left.iter().filter(|(_, value)| is_present(value));
+                let right = right.iter().filter(|(_, value)| is_present(value));
+                left.clone().count() == right.clone().count()
+                    && left
+                        .zip(right)
+                        .all(|((left_key, left), (right_key, right))| {
+                            left_key == right_key && left.eq_treating_bad_as_absent(right)
+                        })
+            }
+            (Hocon::Array(left), Hocon::Array(right)) => {
+                let left = left.iter().filter(|value| is_present(value));
+                let right = right.iter().filter(|value| is_present(value));
+                left.clone().count() == right.clone().count()
+                    && left
+                        .zip(right)
+                        .all(|(left, right)| left.eq_treating_bad_as_absent(right))
+            }
+            (Hocon::BadValue(_), Hocon::BadValue(_)) => true,
+            _ => self == other,
+        }
+    }
+
+    // Set a value at a path with keys separated by `.`, creating missing objects on the way.
+    // Returns `false` if a value on the path is not an object
+    pub(crate) fn set_dotted(&mut self, path: &str, new_value: Hocon) -> bool {
+        let mut keys = path.split('.').peekable();
+        let mut current = self;
+        while let Some(key) = keys.next() {
+            let hash = match current {
+                Hocon::Hash(hash) => hash,
+                _ => return false,
+            };
+            if keys.peek().is_none() {
+                hash.insert(String::from(key), new_value);
+                return true;
+            }
+            current = hash
+                .entry(String::from(key))
+                .or_insert_with(|| Hocon::Hash(LinkedHashMap::new()));
+        }
+        false
+    }
+
+    pub(crate) fn collect_schema_mismatches(
+        &self,
+        schema: &Hocon,
+        path: &str,
+        mismatches: &mut Vec<String>,
+    ) {
+        let display_path = if path.is_empty() { "." } else { path };
+        let sub_path = |key: &str| {
+            if path.is_empty() {
+                String::from(key)
+            } else {
+                format!("{}.{}", path, key)
+            }
+        };
+        match schema {
+            Hocon::Hash(expected) => {
+                if let Hocon::Hash(_) = self {
+                    for (key, sub_schema) in expected {
+                        let sub_path = sub_path(key);
+                        match &self[key.as_ref()] {
+                            Hocon::BadValue(_) => {
+                                mismatches.push(format!("{}: missing value", sub_path))
+                            }
+                            value => {
+                                value.collect_schema_mismatches(sub_schema, &sub_path, mismatches)
+                            }
+                        }
+                    }
+                } else {
+                    mismatches.push(format!("{}: expected hash", display_path));
+                }
+            }
+            Hocon::Array(expected) => match (self, expected.first()) {
+                (Hocon::Array(values), Some(item_schema)) => {
+                    for (i, value) in values.iter().enumerate() {
+                        value.collect_schema_mismatches(
+                            item_schema,
+                            &sub_path(&i.to_string()),
+                            mismatches,
+                        );
+                    }
+                }
+                (Hocon::Array(_), None) => (),
+                _ => mismatches.push(format!("{}: expected array", display_path)),
+            },
+            Hocon::String(expected) => {
+                let valid = match expected.as_ref() {
+                    "integer" => self.as_i64().is_some(),
+                    "real" => self.as_f64().is_some(),
+                    "string" => self.as_string().is_some(),
+                    "boolean" => self.as_bool().is_some(),
+                    "array" => matches!(self, Hocon::Array(_)),
+                    "hash" => matches!(self, Hocon::Hash(_)),
+                    "null" => matches!(self, Hocon::Null),
+                    unknown => {
+                        mismatches.push(format!(
+                            "{}: unknown type \"{}\" in schema",
+                            display_path, unknown
+                        ));
+                        return;
+                    }
+                };
+                if !valid {
+                    mismatches.push(format!("{}: expected {}", display_path, expected));
+                }
+            }
+            _ => mismatches.push(format!("{}: invalid schema", display_path)),
+        }
+    }
+}
+
+mod unit_format {
+    use nom::*;
+
+    named!(
+        parse_float<types::CompleteStr, f64>,
+        complete!(flat_map!(recognize_float, parse_to!(f64)))
+    );
+
+    pub(crate) fn value_and_unit(s: &str) -> Option<(f64, &str)> {
+        match parse_float(types::CompleteStr(s)) {
+            Ok((remaining, float)) => Some((float, &remaining)),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! units_f {
+    ( match $input:expr, $( $first_unit:expr, $( $unit:expr ),* => $scale:expr ),* ) => {
+        match $input {
+            $(
+                Some((value, $first_unit))
+                $(
+                    | Some((value, $unit))
+                )* => Some(value * $scale ),
+            )*
+            _ => None
+        }
+    };
+}
+
+impl Hocon {
+    /// Try to return a value as a size in bytes according to
+    /// [size in bytes format](https://github.com/lightbend/config/blob/master/HOCON.md#size-in-bytes-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string. Negative sizes return `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ size = 1.5KiB }"#)?.hocon()?["size"].as_bytes(),
+    ///     Some(1536)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_bytes(&self) -> Option<u64> {
+        match *self {
+            Hocon::Integer(ref i) if *i >= 0 => Some(*i as u64),
+            // Hocon::Real(ref f) => Some(*f),
+            Hocon::String(ref s) => unit_format::value_and_unit(s)
+                .filter(|(value, _)| *value >= 0.0)
+                .and_then(|(value, unit)| {
+                    Self::bytes_unit_scale(unit.trim()).map(|scale| (value * scale as f64) as u64)
+                }),
+            _ => None,
+        }
+    }
+
+    /// Try to return a value as a size in bytes according to
+    /// [size in bytes format](https://github.com/lightbend/config/blob/master/HOCON.md#size-in-bytes-format),
+    /// like [`as_bytes`](enum.Hocon.html#method.as_bytes), but without losing precision on
+    /// large sizes. Whole numbers are computed with integer math, while fractional sizes are
+    /// rounded to the nearest byte. Negative sizes and sizes that overflow a `u64` return `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ size = 9007199254740993B }"#)?
+    ///         .hocon()?["size"].as_bytes_u64(),
+    ///     Some(9_007_199_254_740_993)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_bytes_u64(&self) -> Option<u64> {
+        match *self {
+            Hocon::Integer(ref i) => u64::try_from(*i).ok(),
+            Hocon::String(ref s) => Self::str_as_bytes(s),
+            _ => None,
+        }
+    }
+
+    /// Try to parse a string as a size in bytes according to
+    /// [size in bytes format](https://github.com/lightbend/config/blob/master/HOCON.md#size-in-bytes-format),
+    /// with the same rules as [`as_bytes_u64`](enum.Hocon.html#method.as_bytes_u64)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::Hocon;
+    /// assert_eq!(Hocon::str_as_bytes("32.5M"), Some(34078720));
+    /// assert_eq!(Hocon::str_as_bytes("a lot"), None);
+    /// ```
+    pub fn str_as_bytes(s: &str) -> Option<u64> {
+        let (value, unit) = unit_format::value_and_unit(s)?;
+        let scale = Self::bytes_unit_scale(unit.trim())?;
+        match s[..s.len() - unit.len()].parse::<u64>() {
+            Ok(whole) => u128::from(whole)
+                .checked_mul(scale)
+                .and_then(|bytes| u64::try_from(bytes).ok()),
+            Err(_) => {
+                let bytes = (value * scale as f64).round();
+                // `u64::MAX as f64` is rounded up to 2^64, which doesn't fit in a `u64`
+                if bytes >= 0.0 && bytes < u64::MAX as f64 {
+                    Some(bytes as u64)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn bytes_unit_scale(unit: &str) -> Option<u128> {
+        match unit {
+            "" | "B" | "b" | "byte" | "bytes" => Some(1),
+            "kB" | "kilobyte" | "kilobytes" => Some(10u128.pow(3)),
+            "MB" | "megabyte" | "megabytes" => Some(10u128.pow(6)),
+            "GB" | "gigabyte" | "gigabytes" => Some(10u128.pow(9)),
+            "TB" | "terabyte" | "terabytes" => Some(10u128.pow(12)),
+            "PB" | "petabyte" | "petabytes" => Some(10u128.pow(15)),
+            "EB" | "exabyte" | "exabytes" => Some(10u128.pow(18)),
+            "ZB" | "zettabyte" | "zettabytes" => Some(10u128.pow(21)),
+            "YB" | "yottabyte" | "yottabytes" => Some(10u128.pow(24)),
+            "K" | "k" | "Ki" | "KiB" | "kibibyte" | "kibibytes" => Some(2u128.pow(10)),
+            "M" | "m" | "Mi" | "MiB" | "mebibyte" | "mebibytes" => Some(2u128.pow(20)),
+            "G" | "g" | "Gi" | "GiB" | "gibibyte" | "gibibytes" => Some(2u128.pow(30)),
+            "T" | "t" | "Ti" | "TiB" | "tebibyte" | "tebibytes" => Some(2u128.pow(40)),
+            "P" | "p" | "Pi" | "PiB" | "pebibyte" | "pebibytes" => Some(2u128.pow(50)),
+            "E" | "e" | "Ei" | "EiB" | "exbibyte" | "exbibytes" => Some(2u128.pow(60)),
+            "Z" | "z" | "Zi" | "ZiB" | "zebibyte" | "zebibytes" => Some(2u128.pow(70)),
+            "Y" | "y" | "Yi" | "YiB" | "yobibyte" | "yobibytes" => Some(2u128.pow(80)),
+            _ => None,
+        }
+    }
+
+    /// Try to return a value as a duration in milliseconds according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
+    ///         .hocon()?["duration"].as_milliseconds(),
+    ///     Some(5400000.0)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_milliseconds(&self) -> Option<f64> {
+        match *self {
+            Hocon::Integer(ref i) => Some(*i as f64),
+            Hocon::Real(ref f) => Some(*f),
+            Hocon::String(ref s) => Self::str_as_milliseconds(s),
+            _ => None,
+        }
+    }
+
+    /// Try to parse a string as a duration in milliseconds according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format),
+    /// with the same rules as [`as_milliseconds`](enum.Hocon.html#method.as_milliseconds)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::Hocon;
+    /// assert_eq!(Hocon::str_as_milliseconds("1.5 hour"), Some(5400000.0));
+    /// assert_eq!(Hocon::str_as_milliseconds("forever"), None);
+    /// ```
+    pub fn str_as_milliseconds(s: &str) -> Option<f64> {
+        units_f!(
+            match unit_format::value_and_unit(s).map(|(value, unit)| (value, unit.trim())),
+            "ns", "nano", "nanos", "nanosecond", "nanoseconds"          => 10.0f64.powf(-6.0),
+            "us", "micro", "micros", "microsecond", "microseconds"      => 10.0f64.powf(-3.0),
+            "", "ms", "milli", "millis", "millisecond", "milliseconds"  => 1.0,
+            "s", "second", "seconds"                                    => 1_000.0,
+            "m", "minute", "minutes"                                    => 1_000.0 * 60.0,
+            "h", "hour", "hours"                                        => 1_000.0 * 60.0 * 60.0,
+            "d", "day", "days"                                          => 1_000.0 * 60.0 * 60.0 * 24.0,
+            "w", "week", "weeks"                                        => 1_000.0 * 60.0 * 60.0 * 24.0 * 7.0,
+            "mo", "month", "months"                                     => 1_000.0 * 60.0 * 60.0 * 24.0 * 30.0,
+            "y", "year", "years"                                        => 1_000.0 * 60.0 * 60.0 * 24.0 * 365.0
+        )
+    }
+
+    /// Try to return a value as a duration in nanoseconds according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
+    ///         .hocon()?["duration"].as_nanoseconds(),
+    ///     Some(5400000000000.0)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_nanoseconds(&self) -> Option<f64> {
+        self.as_milliseconds().map(|v| v * 10.0f64.powf(6.0))
+    }
+
+    /// Try to return a value as a duration in microseconds according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
+    ///         .hocon()?["duration"].as_microseconds(),
+    ///     Some(5400000000.0)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_microseconds(&self) -> Option<f64> {
+        self.as_milliseconds().map(|v| v * 10.0f64.powf(3.0))
+    }
+
+    /// Try to return a value as a duration in seconds according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
+    ///         .hocon()?["duration"].as_seconds(),
+    ///     Some(5400.0)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_seconds(&self) -> Option<f64> {
+        self.as_milliseconds().map(|v| v * 10.0f64.powf(-3.0))
+    }
+
+    /// Try to return a value as a duration in minutes according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
+    ///         .hocon()?["duration"].as_minutes(),
+    ///     Some(90.0)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_minutes(&self) -> Option<f64> {
+        self.as_milliseconds()
+            .map(|v| v * 10.0f64.powf(-3.0) / 60.0)
+    }
+
+    /// Try to return a value as a duration in hours according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
+    ///         .hocon()?["duration"].as_hours(),
+    ///     Some(1.5)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_hours(&self) -> Option<f64> {
+        self.as_milliseconds()
+            .map(|v| v * 10.0f64.powf(-3.0) / 60.0 / 60.0)
+    }
+
+    /// Try to return a value as a duration in days according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hour  }"#)?
+    ///         .hocon()?["duration"].as_days(),
+    ///     Some(0.0625)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_days(&self) -> Option<f64> {
+        self.as_milliseconds()
+            .map(|v| v * 10.0f64.powf(-3.0) / 60.0 / 60.0 / 24.0)
+    }
+
+    /// Try to return a value as a duration in weeks according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 days  }"#)?
+    ///         .hocon()?["duration"].as_weeks(),
+    ///     Some(0.21428571428571427)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_weeks(&self) -> Option<f64> {
+        self.as_milliseconds()
+            .map(|v| v * 10.0f64.powf(-3.0) / 60.0 / 60.0 / 24.0 / 7.0)
+    }
+
+    /// Try to return a value as a duration in months according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 days  }"#)?
+    ///         .hocon()?["duration"].as_months(),
+    ///     Some(0.05)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_months(&self) -> Option<f64> {
+        self.as_milliseconds()
+            .map(|v| v * 10.0f64.powf(-3.0) / 60.0 / 60.0 / 24.0 / 30.0)
+    }
+
+    /// Try to return a value as a duration in years according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 days  }"#)?
+    ///         .hocon()?["duration"].as_years(),
+    ///     Some(0.00410958904109589)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_years(&self) -> Option<f64> {
+        self.as_milliseconds()
+            .map(|v| v * 10.0f64.powf(-3.0) / 60.0 / 60.0 / 24.0 / 365.0)
+    }
+
+    /// Try to return a value as a duration according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
+    ///
+    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
+    /// plus an optional unit string. Negative durations return `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hours  }"#)?
+    ///         .hocon()?["duration"].as_duration(),
+    ///     Some(std::time::Duration::from_secs(5400))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_duration(&self) -> Option<std::time::Duration> {
+        self.as_nanoseconds()
+            .filter(|v| *v >= 0.0)
+            .map(|v| std::time::Duration::from_nanos(v as u64))
+    }
+
+    /// Try to return a value as a duration according to
+    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format),
+    /// like [`as_duration`](enum.Hocon.html#method.as_duration), but without being limited
+    /// to about 584 years. A duration too large to be represented is clamped to
+    /// `Duration::MAX`. Negative durations return `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use hocon::{Hocon, HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// assert_eq!(
+    ///     HoconLoader::new().load_str(r#"{ duration = 1000 years }"#)?
+    ///         .hocon()?["duration"].as_duration_saturating(),
+    ///     Some(std::time::Duration::from_secs(1000 * 365 * 24 * 60 * 60))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_duration_saturating(&self) -> Option<std::time::Duration> {
+        self.as_nanoseconds()
+            .filter(|v| *v >= 0.0)
+            .map(|v| match v {
+                v if v < u64::MAX as f64 => std::time::Duration::from_nanos(v as u64),
+                v => std::time::Duration::try_from_secs_f64(v / 10.0f64.powf(9.0))
+                    .unwrap_or(std::time::Duration::MAX),
+            })
+    }
+}
+
+// Keys and strings that can be written without quotes and parsed back to the same value
+fn is_safe_unquoted(s: &str) -> bool {
+    s.chars().next().map(char::is_alphabetic).unwrap_or(false)
+        && s.chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        && s != "true"
+        && s != "false"
+        && s != "null"
+        && !s.starts_with("include")
+}
+
+// Quote `s` as a JSON string. When `ascii_safe` is set, non-ASCII characters are escaped
+// as `\uXXXX` sequences, using a surrogate pair outside of the Basic Multilingual Plane
+fn quote_string(s: &str, out: &mut String, ascii_safe: bool) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c if ascii_safe && !c.is_ascii() => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    out.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+impl Hocon {
+    /// Render the document as HOCON text on multiple lines, with nested values indented.
+    /// Keys and strings are quoted only when needed, and the order of keys is kept. Parsing
+    /// the result gives back the same document.
+    ///
+    /// A [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) is rendered as `null`,
+    /// followed by a comment with the error.
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ a: 1, b { c: [x, "y z"] } }"#)?.hocon()?;
+    /// assert_eq!(
+    ///     doc.to_string_pretty(),
+    ///     "{\n    a: 1\n    b: {\n        c: [\n            x\n            \"y z\"\n        ]\n    }\n}"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_string_pretty(&self) -> String {
+        let mut out = String::new();
+        self.write_hocon(&mut out, Some(0), false, None, &mut vec![]);
+        out
+    }
+
+    /// Render the document as compact HOCON text on a single line. Keys and strings are
+    /// quoted only when needed, and the order of keys is kept. Parsing the result gives back
+    /// the same document.
+    ///
+    /// A [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) is rendered as `null`.
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ a: 1, b { c: [x, "y z"] } }"#)?.hocon()?;
+    /// assert_eq!(doc.to_hocon_string(), r#"{a:1,b:{c:[x,"y z"]}}"#);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_hocon_string(&self) -> String {
+        let mut out = String::new();
+        self.write_hocon(&mut out, None, false, None, &mut vec![]);
+        out
+    }
+
+    /// Render the document as compact HOCON text on a single line, like
+    /// [`to_hocon_string`](#method.to_hocon_string), but using only ASCII characters: non-ASCII
+    /// characters are escaped as `\uXXXX` sequences in quoted keys and strings. Parsing the
+    /// result gives back the same document.
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ "café": "crème" }"#)?.hocon()?;
+    /// assert_eq!(doc.to_ascii_safe_string(), r#"{"caf\u00e9":"cr\u00e8me"}"#);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_ascii_safe_string(&self) -> String {
+        let mut out = String::new();
+        self.write_hocon(&mut out, None, true, None, &mut vec![]);
+        out
+    }
+
+    /// Render the document as compact HOCON text on a single line, like
+    /// [`to_hocon_string`](#method.to_hocon_string), but writing real numbers with the text
+    /// they had in the loaded documents, as returned by
+    /// [`HoconLoader::number_texts`](struct.HoconLoader.html#method.number_texts).
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let loader = HoconLoader::new()
+    ///     .preserve_number_text()
+    ///     .load_str("{ a: 1e3, b: 1.50 }")?;
+    /// let doc = loader.clone().hocon()?;
+    /// assert_eq!(doc.to_hocon_string(), "{a:1000.0,b:1.5}");
+    /// assert_eq!(
+    ///     doc.to_hocon_string_with_number_texts(&loader.number_texts()?),
+    ///     "{a:1e3,b:1.50}"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_hocon_string_with_number_texts(
+        &self,
+        number_texts: &HashMap<String, String>,
+    ) -> String {
+        let mut out = String::new();
+        self.write_hocon(&mut out, None, false, Some(number_texts), &mut vec![]);
+        out
+    }
+
+    /// Render the document as Java properties, one `key.subkey=value` line for each value.
+    /// Elements of arrays are flattened with their index as key, and `null` or
+    /// [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) values are skipped. Keys and
+    /// values are escaped as Java properties require, and non-ASCII characters are written
+    /// as `\uXXXX` sequences.
+    ///
+    /// ```rust
+    /// # use hocon::{HoconLoader, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let doc = HoconLoader::new().load_str(r#"{ a: 1, b { c: [x, "y z"] } }"#)?.hocon()?;
+    /// assert_eq!(doc.to_properties(), "a=1\nb.c.0=x\nb.c.1=y z\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_properties(&self) -> String {
+        fn escape(s: &str, is_key: bool, out: &mut String) {
+            for (i, c) in s.chars().enumerate() {
+                match c {
+                    '\\' => out.push_str("\\\\"),
+                    '\n' => out.push_str("\\n"),
+                    '\r' => out.push_str("\\r"),
+                    '\t' => out.push_str("\\t"),
+                    '\u{c}' => out.push_str("\\f"),
+                    ' ' if is_key || i == 0 => out.push_str("\\ "),
+                    '=' | ':' | '#' | '!' if is_key => {
+                        out.push('\\');
+                        out.push(c);
+                    }
+                    c if c.is_ascii() && !c.is_ascii_control() => out.push(c),
+                    c => {
+                        let mut units = [0; 2];
+                        for unit in c.encode_utf16(&mut units) {
+                            out.push_str(&format!("\\u{:04x}", unit));
+                        }
+                    }
+                }
+            }
+        }
+        fn write_line(path: &[String], value: &str, out: &mut String) {
+            escape(&path.join("."), true, out);
+            out.push('=');
+            escape(value, false, out);
+            out.push('\n');
+        }
+        fn flatten_into(value: &Hocon, path: &mut Vec<String>, out: &mut String) {
+            match value {
+                Hocon::Hash(hash) => {
+                    for (key, value) in hash {
+                        path.push(key.clone());
+                        flatten_into(value, path, out);
+                        path.pop();
+                    }
+                }
+                Hocon::Array(values) => {
+                    for (i, value) in values.iter().enumerate() {
+                        path.push(i.to_string());
+                        flatten_into(value, path, out);
+                        path.pop();
+                    }
+                }
+                Hocon::Integer(i) => write_line(path, &i.to_string(), out),
+                Hocon::Real(f) => write_line(path, &f.to_string(), out),
+                Hocon::String(s) => write_line(path, s, out),
+                Hocon::Boolean(b) => write_line(path, &b.to_string(), out),
+                Hocon::Null | Hocon::BadValue(_) => (),
+            }
+        }
+
+        let mut out = String::new();
+        flatten_into(self, &mut vec![], &mut out);
+        out
+    }
+
+    // Write the value to `out`, on multiple lines indented at `indent` level if it is set,
+    // or on a single line otherwise. Real numbers whose `path` is in `number_texts` are
+    // written with that text
+    fn write_hocon(
+        &self,
+        out: &mut String,
+        indent: Option<usize>,
+        ascii_safe: bool,
+        number_texts: Option<&HashMap<String, String>>,
+        path: &mut Vec<String>,
+    ) {
+        fn new_line(out: &mut String, level: usize) {
+            out.push('\n');
+            out.push_str(&"    ".repeat(level));
+        }
+        fn error_comment(value: &Hocon, out: &mut String, indent: Option<usize>) {
+            if let (Some(_), Hocon::BadValue(err)) = (indent, value) {
+                out.push_str(&format!(" # {}", err));
+            }
+        }
+
+        let is_unquoted = |s: &str| is_safe_unquoted(s) && (!ascii_safe || s.is_ascii());
+
+        match self {
+            Hocon::Hash(hash) if hash.is_empty() => out.push_str("{}"),
+            Hocon::Hash(hash) => {
+                out.push('{');
+                for (i, (key, value)) in hash.iter().enumerate() {
+                    match indent {
+                        Some(level) => new_line(out, level + 1),
+                        None if i > 0 => out.push(','),
+                        None => (),
+                    }
+                    if is_unquoted(key) {
+                        out.push_str(key);
+                    } else {
+                        quote_string(key, out, ascii_safe);
+                    }
+                    out.push_str(if indent.is_some() { ": " } else { ":" });
+                    path.push(key.clone());
+                    value.write_hocon(
+                        out,
+                        indent.map(|level| level + 1),
+                        ascii_safe,
+                        number_texts,
+                        path,
+                    );
+                    path.pop();
+                    error_comment(value, out, indent);
+                }
+                if let Some(level) = indent {
+                    new_line(out, level);
+                }
+                out.push('}');
+            }
+            Hocon::Array(values) if values.is_empty() => out.push_str("[]"),
+            Hocon::Array(values) => {
+                out.push('[');
+                for (i, value) in values.iter().enumerate() {
+                    match indent {
+                        Some(level) => new_line(out, level + 1),
+                        None if i > 0 => out.push(','),
+                        None => (),
+                    }
+                    path.push(i.to_string());
+                    value.write_hocon(
+                        out,
+                        indent.map(|level| level + 1),
+                        ascii_safe,
+                        number_texts,
+                        path,
+                    );
+                    path.pop();
+                    error_comment(value, out, indent);
+                }
+                if let Some(level) = indent {
+                    new_line(out, level);
+                }
+                out.push(']');
+            }
+            Hocon::Integer(i) => out.push_str(&i.to_string()),
+            Hocon::Real(f) => match number_texts.and_then(|texts| texts.get(&path.join("."))) {
+                // the original text of a number is always parsed back as a real number
+                Some(text) => out.push_str(text),
+                None => {
+                    let real = f.to_string();
+                    out.push_str(&real);
+                    // keep it a real number when parsed back
+                    if f.is_finite() && !real.contains('.') {
+                        out.push_str(".0");
+                    }
+                }
+            },
+            Hocon::String(s) if is_unquoted(s) => out.push_str(s),
+            Hocon::String(s) => quote_string(s, out, ascii_safe),
+            Hocon::Boolean(b) => out.push_str(&b.to_string()),
+            Hocon::Null | Hocon::BadValue(_) => out.push_str("null"),
+        }
+    }
+}
+
+/// Display a value in a human-readable form. Scalars are displayed as their value, arrays
+/// and objects are displayed in a HOCON-like form, quoting strings and keys only when
+/// needed, and a [`Hocon::BadValue`](enum.Hocon.html#variant.BadValue) is displayed as its
+/// error.
+///
+/// ```rust
+/// # use hocon::{HoconLoader, Error};
+/// # fn main() -> Result<(), Error> {
+/// let doc = HoconLoader::new().load_str(r#"{ a: 1, b { c: [x, "y z"] } }"#)?.hocon()?;
+/// assert_eq!(doc.to_string(), r#"{a: 1, b: {c: [x, "y z"]}}"#);
+/// assert_eq!(doc["b"]["c"][1].to_string(), "y z");
+/// # Ok(())
+/// # }
+/// ```
+impl std::fmt::Display for Hocon {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fn quoted_if_needed(s: &str, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            if is_safe_unquoted(s) {
+                f.write_str(s)
+            } else {
+                let mut out = String::new();
+                quote_string(s, &mut out, false);
+                f.write_str(&out)
+            }
+        }
+        fn nested(value: &Hocon, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            match value {
+                Hocon::String(s) => quoted_if_needed(s, f),
+                value => write!(f, "{}", value),
+            }
+        }
+
+        match self {
+            Hocon::Real(v) => write!(f, "{}", v),
+            Hocon::Integer(v) => write!(f, "{}", v),
+            Hocon::String(v) => f.write_str(v),
+            Hocon::Boolean(v) => write!(f, "{}", v),
+            Hocon::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    nested(value, f)?;
+                }
+                f.write_str("]")
+            }
+            Hocon::Hash(hash) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in hash.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    quoted_if_needed(key, f)?;
+                    f.write_str(": ")?;
+                    nested(value, f)?;
+                }
+                f.write_str("}")
+            }
+            Hocon::Null => f.write_str("null"),
+            Hocon::BadValue(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Hocon {
+    /// Deserialize the loaded documents to the target type
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Deserialization`](enum.Error.html#variant.Deserialization) if there was a
+    /// serde error during deserialization (missing required field, type issue, ...)
+    ///
+    /// # Additional errors in strict mode
+    ///
+    /// * [`Error::Include`](enum.Error.html#variant.Include) if there was an issue with an
+    /// included file
+    /// * [`Error::KeyNotFound`](enum.Error.html#variant.KeyNotFound) if there is a substitution
+    /// with a key that is not present in the document
+    /// * [`Error::DisabledExternalUrl`](enum.Error.html#variant.DisabledExternalUrl) if crate
+    /// was built without feature `url-support` and an `include url("...")` was found
+    #[cfg(feature = "serde-support")]
+    pub fn resolve<'de, T>(self) -> Result<T, crate::Error>
+    where
+        T: ::serde::Deserialize<'de>,
+    {
+        crate::serde::from_hocon(self).map_err(|err| crate::Error::Deserialization {
+            message: err.message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn access_as_f64_locale() {
+        let val = |s: &str| Hocon::String(String::from(s));
+        assert_eq!(val("1,5").as_f64_locale(','), Some(1.5));
+        assert_eq!(val("-0,25").as_f64_locale(','), Some(-0.25));
+        assert_eq!(val("3").as_f64_locale(','), Some(3.0));
+        assert_eq!(val("1.5").as_f64_locale('.'), Some(1.5));
+        assert_eq!(Hocon::Real(2.5).as_f64_locale(','), Some(2.5));
+
+        assert_eq!(val("1.5").as_f64_locale(','), None);
+        assert_eq!(val("1.000,5").as_f64_locale(','), None);
+        assert_eq!(val("1,000,5").as_f64_locale(','), None);
+        assert_eq!(val("1,5").as_f64_locale('.'), None);
+    }
+
+    #[test]
+    fn access_as_f64_from_bool() {
+        assert_eq!(Hocon::Boolean(true).as_f64(), None);
+        assert_eq!(Hocon::Boolean(true).as_f64_from_bool(), Some(1.0));
+        assert_eq!(Hocon::Boolean(false).as_f64_from_bool(), Some(0.0));
+        assert_eq!(Hocon::Integer(3).as_f64_from_bool(), Some(3.0));
+        assert_eq!(Hocon::Null.as_f64_from_bool(), None);
+    }
+
+    #[test]
+    fn access_as_f64_nullable() {
+        assert_eq!(Hocon::Null.as_f64_nullable(), Some(None));
+        assert_eq!(Hocon::Real(1.5).as_f64_nullable(), Some(Some(1.5)));
+        assert_eq!(Hocon::Integer(2).as_f64_nullable(), Some(Some(2.0)));
+        assert_eq!(
+            Hocon::String(String::from("not a number")).as_f64_nullable(),
+            None
+        );
+    }
+
+    #[test]
+    fn get_by_key_or_index() {
+        let mut hash = LinkedHashMap::new();
+        hash.insert(String::from("a"), Hocon::Integer(1));
+        hash.insert(String::from("1"), Hocon::Integer(3));
+        hash.insert(String::from("0"), Hocon::Integer(2));
+        let doc = Hocon::Hash(hash);
+        assert_eq!(doc.get("a"), Some(&Hocon::Integer(1)));
+        assert_eq!(doc.get("b"), None);
+        assert_eq!(doc.get_index(0), Some(&Hocon::Integer(2)));
+        assert_eq!(doc.get_index(1), Some(&Hocon::Integer(3)));
+        assert_eq!(doc.get_index(2), None);
+
+        let array = Hocon::Array(vec![Hocon::Null]);
+        assert_eq!(array.get_index(0), Some(&Hocon::Null));
+        assert_eq!(array.get_index(1), None);
+        assert_eq!(array.get("a"), None);
+        assert_eq!(Hocon::Integer(1).get("a"), None);
+        assert_eq!(Hocon::Integer(1).get_index(0), None);
+    }
+
+    #[test]
+    fn iterate_over_entries_and_elements() {
+        let mut hash = LinkedHashMap::new();
+        hash.insert(String::from("b"), Hocon::Integer(1));
+        hash.insert(String::from("a"), Hocon::Boolean(true));
+        let doc = Hocon::Hash(hash);
+        assert_eq!(doc.entries().count(), 2);
+        assert_eq!(
+            doc.entries().collect::<Vec<_>>(),
+            vec![
+                (&String::from("b"), &Hocon::Integer(1)),
+                (&String::from("a"), &Hocon::Boolean(true))
+            ]
+        );
+        assert_eq!(doc.elements().count(), 0);
+
+        let array = Hocon::Array(vec![Hocon::Integer(1), Hocon::Null]);
+        assert_eq!(
+            array.elements().collect::<Vec<_>>(),
+            vec![&Hocon::Integer(1), &Hocon::Null]
+        );
+        assert_eq!(array.entries().count(), 0);
+        assert_eq!(Hocon::Null.elements().count(), 0);
+    }
+
+    #[test]
+    fn merge_all_documents() {
+        let doc = |s: &str| {
+            crate::HoconLoader::new()
+                .load_str(s)
+                .expect("during test")
+                .hocon()
+                .expect("during test")
+        };
+        let merged = Hocon::merge_all(vec![
+            doc(r#"{ a: 1, b { c: 1, d: [1, 2] }, e: { f: 1 } }"#),
+            doc(r#"{ a: 2, b { d: [3] }, g: 1 }"#),
+            doc(r#"{ a: 3, b { h: 1 }, e: 4 }"#),
+        ]);
+
+        assert_eq!(
+            merged,
+            doc(r#"{ a: 3, b { c: 1, d: [3], h: 1 }, e: 4, g: 1 }"#)
+        );
+        assert_eq!(
+            merged
+                .entries()
+                .map(|(key, _)| key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "e", "g"]
+        );
+        assert_eq!(Hocon::merge_all(vec![]), Hocon::Hash(LinkedHashMap::new()));
+        assert_eq!(
+            Hocon::merge_all(vec![doc("{ a: 1 }"), Hocon::Integer(5)]),
+            Hocon::Integer(5)
+        );
+    }
+
+    #[test]
+    fn properties_round_trip() {
+        let properties =
+            "a=1\nb.c=some value\nb.d=\\ leading space\nkey\\ with\\:chars=x\\\\y\ne=caf\\u00e9\n";
+        let load = |s: &str| {
+            crate::HoconLoader::new()
+                .load_str_as(s, crate::FileType::Properties)
+                .expect("during test")
+                .hocon()
+                .expect("during test")
+        };
+        let doc = load(properties);
+        assert_eq!(
+            doc["b"]["d"].as_string(),
+            Some(String::from(" leading space"))
+        );
+        assert_eq!(
+            doc["key with:chars"].as_string(),
+            Some(String::from("x\\y"))
+        );
+        assert_eq!(doc["e"].as_string(), Some(String::from("caf\u{e9}")));
+
+        // keys from properties are not ordered, compare each value
+        let reloaded = load(&doc.to_properties());
+        for key in &["a", "b.c", "b.d", "key with:chars", "e"] {
+            assert_eq!(reloaded.at_path(key), doc.at_path(key));
+        }
+        assert_eq!(reloaded.entries().count(), doc.entries().count());
+
+        let doc = crate::HoconLoader::new()
+            .load_str(r#"{ a: 1.5, b: [true, null], c: "multi\nline" }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc.to_properties(), "a=1.5\nb.0=true\nc=multi\\nline\n");
+    }
+
+    #[test]
+    fn merge_documents() {
+        let doc = |s: &str| {
+            crate::HoconLoader::new()
+                .load_str(s)
+                .expect("during test")
+                .hocon()
+                .expect("during test")
+        };
+        let defaults = doc(r#"{ a: 1, b { c: 1, d { e: 1, f: 2 } }, g: [1, 2], h: 1 }"#);
+        let overrides = doc(r#"{ b { d { f: 3 }, i: 4 }, j: null, g: [3], h: null}"#);
+
+        assert_eq!(
+            defaults.merge(overrides),
+            doc(r#"{ a: 1, b { c: 1, d { e: 1, f: 3 }, i: 4 }, g: [3] }"#)
+        );
+        assert_eq!(
+            doc("{ a: { b: 1 } }").merge(doc("{ a: 2 }")),
+            doc("{ a: 2 }")
+        );
+    }
+
+    #[test]
+    fn compare_treating_bad_as_absent() {
+        let missing = || {
+            Hocon::BadValue(crate::Error::KeyNotFound {
+                key: String::from("missing"),
+            })
+        };
+        let mut with_bad = LinkedHashMap::new();
+        with_bad.insert(String::from("a"), Hocon::Integer(1));
+        with_bad.insert(String::from("b"), missing());
+        let mut nested = LinkedHashMap::new();
+        nested.insert(String::from("c"), missing());
+        with_bad.insert(
+            String::from("d"),
+            Hocon::Array(vec![missing(), Hocon::Hash(nested), Hocon::Integer(2)]),
+        );
+
+        let mut expected = LinkedHashMap::new();
+        expected.insert(String::from("a"), Hocon::Integer(1));
+        expected.insert(
+            String::from("d"),
+            Hocon::Array(vec![Hocon::Hash(LinkedHashMap::new()), Hocon::Integer(2)]),
+        );
+
+        let with_bad = Hocon::Hash(with_bad);
+        let mut expected = Hocon::Hash(expected);
+        assert_ne!(with_bad, expected);
+        assert!(with_bad.eq_treating_bad_as_absent(&expected));
+        assert!(expected.eq_treating_bad_as_absent(&with_bad));
+        assert!(missing().eq_treating_bad_as_absent(&missing()));
+
+        if let Hocon::Hash(hash) = &mut expected {
+            hash.insert(String::from("a"), Hocon::Integer(3));
+        }
+        assert!(!with_bad.eq_treating_bad_as_absent(&expected));
+        assert!(!missing().eq_treating_bad_as_absent(&Hocon::Null));
+    }
+
+    #[test]
+    fn display_values() {
+        assert_eq!(Hocon::Real(1.5).to_string(), "1.5");
+        assert_eq!(Hocon::Integer(-3).to_string(), "-3");
+        assert_eq!(Hocon::String(String::from("a b")).to_string(), "a b");
+        assert_eq!(Hocon::Boolean(true).to_string(), "true");
+        assert_eq!(Hocon::Null.to_string(), "null");
+        assert_eq!(
+            Hocon::BadValue(crate::Error::MissingKey).to_string(),
+            "Error getting a value because key is not present"
+        );
+        assert_eq!(Hocon::Array(vec![]).to_string(), "[]");
+        assert_eq!(
+            Hocon::Array(vec![
+                Hocon::Integer(1),
+                Hocon::String(String::from("a")),
+                Hocon::String(String::from("b c")),
+                Hocon::Null
+            ])
+            .to_string(),
+            r#"[1, a, "b c", null]"#
+        );
+
+        let mut inner = LinkedHashMap::new();
+        inner.insert(String::from("x"), Hocon::Boolean(false));
+        let mut hash = LinkedHashMap::new();
+        hash.insert(String::from("b"), Hocon::Hash(inner));
+        hash.insert(String::from("a.b"), Hocon::Real(2.0));
+        assert_eq!(
+            Hocon::Hash(hash).to_string(),
+            r#"{b: {x: false}, "a.b": 2}"#
+        );
+        assert_eq!(Hocon::Hash(LinkedHashMap::new()).to_string(), "{}");
+    }
+
+    #[test]
+    fn access_first_and_last() {
+        let array = Hocon::Array(vec![Hocon::Integer(1), Hocon::Null, Hocon::Integer(3)]);
+        assert_eq!(array.first(), Some(&Hocon::Integer(1)));
+        assert_eq!(array.last(), Some(&Hocon::Integer(3)));
+
+        let empty = Hocon::Array(vec![]);
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.last(), None);
+
+        assert_eq!(Hocon::Integer(1).first(), None);
+        assert_eq!(Hocon::Integer(1).last(), None);
+    }
+
+    #[test]
+    fn access_as_fixed_array() {
+        let array = Hocon::Array(vec![
+            Hocon::Integer(1),
+            Hocon::Integer(2),
+            Hocon::Integer(3),
+        ]);
+        assert_eq!(
+            array.as_fixed_array::<3>(),
+            Some([Hocon::Integer(1), Hocon::Integer(2), Hocon::Integer(3)])
+        );
+        assert_eq!(array.as_fixed_array::<2>(), None);
+        assert_eq!(array.as_fixed_array::<4>(), None);
+        assert_eq!(Hocon::Integer(1).as_fixed_array::<1>(), None);
+    }
+
+    #[test]
+    fn access_as_integers_in_range() {
+        assert_eq!(Hocon::Integer(i64::MAX).as_u64(), Some(i64::MAX as u64));
+        assert_eq!(Hocon::Integer(0).as_u64(), Some(0));
+        assert_eq!(Hocon::Integer(-1).as_u64(), None);
+        assert_eq!(Hocon::String(u64::MAX.to_string()).as_u64(), Some(u64::MAX));
+        assert_eq!(Hocon::String(String::from("-1")).as_u64(), None);
+
+        assert_eq!(Hocon::Integer(i32::MAX as i64).as_i32(), Some(i32::MAX));
+        assert_eq!(Hocon::Integer(i32::MIN as i64).as_i32(), Some(i32::MIN));
+        assert_eq!(Hocon::Integer(i32::MAX as i64 + 1).as_i32(), None);
+        assert_eq!(Hocon::Integer(i32::MIN as i64 - 1).as_i32(), None);
+        assert_eq!(Hocon::String(String::from("-12")).as_i32(), Some(-12));
+
+        assert_eq!(Hocon::Integer(u32::MAX as i64).as_u32(), Some(u32::MAX));
+        assert_eq!(Hocon::Integer(u32::MAX as i64 + 1).as_u32(), None);
+        assert_eq!(Hocon::Integer(-1).as_u32(), None);
+
+        assert_eq!(Hocon::Integer(42).as_usize(), Some(42));
+        assert_eq!(Hocon::Integer(-1).as_usize(), None);
+        assert_eq!(Hocon::Real(1.0).as_usize(), None);
+    }
+
+    #[test]
+    fn access_as_kv_pairs() {
+        let pairs = Hocon::Array(vec![
+            Hocon::Array(vec![Hocon::String(String::from("b")), Hocon::Integer(1)]),
+            Hocon::Array(vec![Hocon::Integer(2), Hocon::Null]),
+        ]);
+        assert_eq!(
+            pairs.as_kv_pairs(),
+            Some(vec![
+                (String::from("b"), &Hocon::Integer(1)),
+                (String::from("2"), &Hocon::Null)
+            ])
+        );
+        assert_eq!(Hocon::Array(vec![]).as_kv_pairs(), Some(vec![]));
+
+        let too_long = Hocon::Array(vec![Hocon::Array(vec![
+            Hocon::String(String::from("a")),
+            Hocon::Integer(1),
+            Hocon::Integer(2),
+        ])]);
+        assert_eq!(too_long.as_kv_pairs(), None);
+        let bad_key = Hocon::Array(vec![Hocon::Array(vec![
+            Hocon::Array(vec![]),
+            Hocon::Integer(1),
+        ])]);
+        assert_eq!(bad_key.as_kv_pairs(), None);
+        assert_eq!(Hocon::Integer(1).as_kv_pairs(), None);
+    }
+
+    #[test]
+    fn try_from_string_collections() {
+        let array = Hocon::Array(vec![
+            Hocon::String(String::from("a")),
+            Hocon::String(String::from("b")),
+        ]);
+        assert_eq!(
+            Vec::<String>::try_from(array),
+            Ok(vec![String::from("a"), String::from("b")])
+        );
+        assert_eq!(
+            Vec::<String>::try_from(Hocon::Array(vec![Hocon::Array(vec![])])),
+            Err(crate::Error::TypeMismatch {
+                expected: String::from("an array of strings")
+            })
+        );
+
+        let hash = Hocon::Hash(
+            vec![(String::from("a"), Hocon::String(String::from("b")))]
+                .into_iter()
+                .collect(),
+        );
+        let mut expected = HashMap::new();
+        expected.insert(String::from("a"), String::from("b"));
+        assert_eq!(HashMap::<String, String>::try_from(hash), Ok(expected));
+        assert_eq!(
+            HashMap::<String, String>::try_from(Hocon::String(String::from("a"))),
+            Err(crate::Error::TypeMismatch {
+                expected: String::from("a hash of strings")
+            })
+        );
+    }
+
+    #[test]
+    fn render_as_hocon_text() {
+        let val = Hocon::Hash(
+            vec![
+                (String::from("a.b"), Hocon::Real(2.0)),
+                (String::from("c"), Hocon::String(String::from("true"))),
+                (
+                    String::from("d"),
+                    Hocon::Array(vec![Hocon::BadValue(crate::Error::MissingKey)]),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert_eq!(val.to_hocon_string(), r#"{"a.b":2.0,c:"true",d:[null]}"#);
+        assert_eq!(
+            val.to_string_pretty(),
+            "{\n    \"a.b\": 2.0\n    c: \"true\"\n    d: [\n        null # Error getting a value because key is not present\n    ]\n}"
+        );
+    }
+
+    #[test]
+    fn access_as_money() {
+        assert_eq!(
+            Hocon::String(String::from("$1.50")).as_money(),
+            Some((1.5, Some(String::from("$"))))
+        );
+        assert_eq!(
+            Hocon::String(String::from("1.50 USD")).as_money(),
+            Some((1.5, Some(String::from("USD"))))
+        );
+        assert_eq!(
+            Hocon::String(String::from("1.50")).as_money(),
+            Some((1.5, None))
+        );
+        assert_eq!(Hocon::Real(1.5).as_money(), Some((1.5, None)));
+        assert_eq!(Hocon::String(String::from("$1.50 USD")).as_money(), None);
+        assert_eq!(Hocon::String(String::from("1.50 U5D")).as_money(), None);
+        assert_eq!(Hocon::String(String::from("USD")).as_money(), None);
     }
 
-    /// Try to return a value as a duration in years according to
-    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
-    ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use hocon::{Hocon, HoconLoader, Error};
-    /// # fn main() -> Result<(), Error> {
-    /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 days  }"#)?
-    ///         .hocon()?["duration"].as_years(),
-    ///     Some(0.00410958904109589)
-    /// );
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn as_years(&self) -> Option<f64> {
-        self.as_milliseconds()
-            .map(|v| v * 10.0f64.powf(-3.0) / 60.0 / 60.0 / 24.0 / 365.0)
+    #[test]
+    fn access_as_enum_index() {
+        let allowed = ["debug", "info", "warn"];
+        assert_eq!(
+            Hocon::String(String::from("info")).as_enum_index(&allowed),
+            Some(1)
+        );
+        assert_eq!(
+            Hocon::String(String::from("trace")).as_enum_index(&allowed),
+            None
+        );
+        assert_eq!(
+            Hocon::String(String::from("INFO")).as_enum_index(&allowed),
+            None
+        );
+        assert_eq!(Hocon::Integer(1).as_enum_index(&allowed), None);
     }
 
-    /// Try to return a value as a duration according to
-    /// [duration format](https://github.com/lightbend/config/blob/master/HOCON.md#duration-format).
-    ///
-    /// Bare numbers are taken to be in bytes already, while strings are parsed as a number
-    /// plus an optional unit string.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use hocon::{Hocon, HoconLoader, Error};
-    /// # fn main() -> Result<(), Error> {
-    /// assert_eq!(
-    ///     HoconLoader::new().load_str(r#"{ duration = 1.5 hours  }"#)?
-    ///         .hocon()?["duration"].as_duration(),
-    ///     Some(std::time::Duration::from_secs(5400))
-    /// );
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn as_duration(&self) -> Option<std::time::Duration> {
-        self.as_nanoseconds()
-            .map(|v| std::time::Duration::from_nanos(v as u64))
+    #[test]
+    fn rename_key_in_hash() {
+        let mut val = Hocon::Hash(
+            vec![
+                (String::from("a"), Hocon::Integer(1)),
+                (String::from("b"), Hocon::Integer(2)),
+                (String::from("c"), Hocon::Integer(3)),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert!(val.rename_key("b", "d"));
+        assert_eq!(val["d"], Hocon::Integer(2));
+        assert_eq!(val["b"], NOT_FOUND);
+        if let Hocon::Hash(hash) = &val {
+            assert_eq!(hash.keys().collect::<Vec<_>>(), vec!["a", "d", "c"]);
+        }
+
+        assert!(!val.rename_key("missing", "e"));
+        assert!(!val.rename_key("a", "c"));
+        assert_eq!(val["a"], Hocon::Integer(1));
+        assert_eq!(val["c"], Hocon::Integer(3));
     }
-}
 
-impl Hocon {
-    /// Deserialize the loaded documents to the target type
-    ///
-    /// # Errors
-    ///
-    /// * [`Error::Deserialization`](enum.Error.html#variant.Deserialization) if there was a
-    /// serde error during deserialization (missing required field, type issue, ...)
-    ///
-    /// # Additional errors in strict mode
-    ///
-    /// * [`Error::Include`](enum.Error.html#variant.Include) if there was an issue with an
-    /// included file
-    /// * [`Error::KeyNotFound`](enum.Error.html#variant.KeyNotFound) if there is a substitution
-    /// with a key that is not present in the document
-    /// * [`Error::DisabledExternalUrl`](enum.Error.html#variant.DisabledExternalUrl) if crate
-    /// was built without feature `url-support` and an `include url("...")` was found
-    #[cfg(feature = "serde-support")]
-    pub fn resolve<'de, T>(self) -> Result<T, crate::Error>
-    where
-        T: ::serde::Deserialize<'de>,
-    {
-        crate::serde::from_hocon(self).map_err(|err| crate::Error::Deserialization {
-            message: err.message,
-        })
+    #[test]
+    fn dedup_array() {
+        let mut val = Hocon::Array(vec![
+            Hocon::Integer(3),
+            Hocon::String(String::from("a")),
+            Hocon::Real(3.0),
+            Hocon::Integer(1),
+            Hocon::String(String::from("a")),
+            Hocon::Integer(3),
+        ]);
+        val.dedup();
+        assert_eq!(
+            val,
+            Hocon::Array(vec![
+                Hocon::Integer(3),
+                Hocon::String(String::from("a")),
+                Hocon::Integer(1),
+            ])
+        );
+
+        let mut val = Hocon::Integer(3);
+        val.dedup();
+        assert_eq!(val, Hocon::Integer(3));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn get_array_of_ports() {
+        let val = Hocon::Hash(
+            vec![
+                (
+                    String::from("ports"),
+                    Hocon::Array(vec![Hocon::Integer(80), Hocon::String(String::from("443"))]),
+                ),
+                (
+                    String::from("invalid"),
+                    Hocon::Array(vec![
+                        Hocon::Integer(80),
+                        Hocon::String(String::from("http")),
+                    ]),
+                ),
+                (
+                    String::from("negative"),
+                    Hocon::Array(vec![Hocon::Integer(-1)]),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert_eq!(val.get_array_of::<u16>("ports"), Some(vec![80, 443]));
+        assert_eq!(val.get_array_of::<u16>("invalid"), None);
+        assert_eq!(val.get_array_of::<u16>("negative"), None);
+        assert_eq!(val.get_array_of::<u16>("missing"), None);
+    }
 
     #[test]
     fn access_on_string() {
@@ -589,6 +2751,101 @@ mod tests {
         assert_eq!(val["a"], INVALID_KEY);
     }
 
+    #[test]
+    fn access_as_str() {
+        let val = Hocon::String(String::from("test"));
+        let borrowed = val.as_str().expect("during test");
+        assert_eq!(borrowed, "test");
+        if let Hocon::String(ref s) = val {
+            assert_eq!(borrowed.as_ptr(), s.as_ptr());
+        }
+
+        assert_eq!(Hocon::Boolean(true).as_str(), None);
+        assert_eq!(Hocon::Integer(5).as_str(), None);
+        assert_eq!(Hocon::Real(1.5).as_str(), None);
+        assert_eq!(Hocon::Null.as_str(), None);
+    }
+
+    #[test]
+    fn access_string_matches() {
+        let val = Hocon::String(String::from("service.eu-west.prod"));
+        assert!(val.string_matches("service.*.prod"));
+        assert!(val.string_matches("service.eu-????.*"));
+        assert!(val.string_matches("*"));
+        assert!(val.string_matches("*prod"));
+        assert!(val.string_matches("service.eu-west.prod"));
+
+        assert!(!val.string_matches("service.*.dev"));
+        assert!(!val.string_matches("service.eu-???.prod"));
+        assert!(!val.string_matches("service"));
+        assert!(!val.string_matches(""));
+        assert!(Hocon::String(String::new()).string_matches("*"));
+        assert!(Hocon::String(String::from("ab")).string_matches("*a*b*"));
+        assert!(!Hocon::String(String::from("ab")).string_matches("a?b"));
+
+        assert!(!Hocon::Integer(5).string_matches("*"));
+        assert!(!Hocon::Boolean(true).string_matches("true"));
+    }
+
+    #[test]
+    fn access_as_string_interpolated() {
+        let mut vars = HashMap::new();
+        vars.insert(String::from("name"), String::from("world"));
+
+        let val = Hocon::String(String::from("hello ${name}, ${ name }!"));
+        assert_eq!(
+            val.as_string_interpolated(&vars),
+            Some(String::from("hello world, world!"))
+        );
+        let val = Hocon::String(String::from("hello ${missing} ${name"));
+        assert_eq!(
+            val.as_string_interpolated(&vars),
+            Some(String::from("hello ${missing} ${name"))
+        );
+        assert_eq!(
+            Hocon::Integer(5).as_string_interpolated(&vars),
+            Some(String::from("5"))
+        );
+        assert_eq!(Hocon::Null.as_string_interpolated(&vars), None);
+    }
+
+    #[test]
+    fn access_type_name() {
+        assert_eq!(Hocon::Integer(1).type_name(), "integer");
+        assert_eq!(Hocon::Real(1.5).type_name(), "real");
+        assert_eq!(Hocon::String(String::from("a")).type_name(), "string");
+        assert_eq!(Hocon::Boolean(true).type_name(), "boolean");
+        assert_eq!(Hocon::Array(vec![]).type_name(), "array");
+        assert_eq!(Hocon::Hash(LinkedHashMap::new()).type_name(), "hash");
+        assert_eq!(Hocon::Null.type_name(), "null");
+        assert_eq!(
+            Hocon::BadValue(crate::Error::MissingKey).type_name(),
+            "bad value"
+        );
+    }
+
+    #[test]
+    fn access_bad_value_message() {
+        assert_eq!(
+            Hocon::BadValue(crate::Error::InvalidKey).bad_value_message(),
+            Some(String::from(
+                "Error getting a value because of an invalid key type"
+            ))
+        );
+        assert_eq!(Hocon::Integer(1).bad_value_message(), None);
+        assert_eq!(Hocon::Null.bad_value_message(), None);
+    }
+
+    #[test]
+    fn access_is_blank() {
+        assert!(Hocon::String(String::new()).is_blank());
+        assert!(Hocon::String(String::from(" \t\n")).is_blank());
+        assert!(!Hocon::String(String::from(" a ")).is_blank());
+        assert!(!Hocon::String(String::from("1.5")).is_blank());
+        assert!(!Hocon::Null.is_blank());
+        assert!(!Hocon::Array(vec![]).is_blank());
+    }
+
     #[test]
     fn access_on_real() {
         let val = Hocon::Real(5.6);
@@ -601,6 +2858,41 @@ mod tests {
         assert_eq!(val["a"], INVALID_KEY);
     }
 
+    #[test]
+    fn access_on_plain_string() {
+        assert_eq!(
+            Hocon::Real(1.5e20).as_plain_string(),
+            Some(String::from("150000000000000000000"))
+        );
+        assert_eq!(
+            Hocon::Real(-2.5e-8).as_plain_string(),
+            Some(String::from("-0.000000025"))
+        );
+        assert_eq!(Hocon::Real(f64::INFINITY).as_plain_string(), None);
+        assert_eq!(Hocon::Integer(5).as_plain_string(), Some(String::from("5")));
+        assert_eq!(
+            Hocon::String(String::from("1e20")).as_plain_string(),
+            Some(String::from("1e20"))
+        );
+    }
+
+    #[test]
+    fn access_on_float_literals() {
+        assert_eq!(Hocon::Real(1.5).as_f32(), Some(1.5));
+        assert_eq!(Hocon::String(String::from("1.5")).as_f32(), Some(1.5));
+        assert_eq!(Hocon::Integer(3).as_f32(), Some(3.0));
+        for literal in &["inf", "-inf", "infinity", "nan", "NaN"] {
+            let val = Hocon::String(String::from(*literal));
+            assert_eq!(val.as_f64(), None);
+            assert_eq!(val.as_f32(), None);
+        }
+        assert_eq!(Hocon::Real(1e300).as_f64(), Some(1e300));
+        assert_eq!(Hocon::Real(1e300).as_f32(), None);
+        assert_eq!(Hocon::Real(-1e39).as_f32(), None);
+        assert_eq!(Hocon::String(String::from("1e400")).as_f64(), None);
+        assert_eq!(Hocon::Boolean(true).as_f32(), None);
+    }
+
     #[test]
     fn access_on_integer() {
         let val = Hocon::Integer(5);
@@ -613,6 +2905,30 @@ mod tests {
         assert_eq!(val["a"], INVALID_KEY);
     }
 
+    #[test]
+    fn access_as_bool_extended() {
+        let truthy = ["enabled", "oui"];
+        let falsy = ["disabled", "non"];
+        let val = |s: &str| Hocon::String(String::from(s));
+
+        assert_eq!(val("enabled").as_bool_extended(&truthy, &falsy), Some(true));
+        assert_eq!(val("oui").as_bool_extended(&truthy, &falsy), Some(true));
+        assert_eq!(
+            val("disabled").as_bool_extended(&truthy, &falsy),
+            Some(false)
+        );
+        assert_eq!(val("yes").as_bool_extended(&truthy, &falsy), Some(true));
+        assert_eq!(val("off").as_bool_extended(&truthy, &falsy), Some(false));
+        assert_eq!(
+            Hocon::Boolean(true).as_bool_extended(&truthy, &falsy),
+            Some(true)
+        );
+
+        assert_eq!(val("maybe").as_bool_extended(&truthy, &falsy), None);
+        assert_eq!(val("enabled").as_bool_extended(&[], &[]), None);
+        assert_eq!(Hocon::Integer(1).as_bool_extended(&truthy, &falsy), None);
+    }
+
     #[test]
     fn access_on_boolean_false() {
         let val = Hocon::Boolean(false);
@@ -727,6 +3043,187 @@ mod tests {
         assert_eq!(val["2"], Hocon::Integer(7));
     }
 
+    #[test]
+    fn access_as_flat_string_vec() {
+        let val = Hocon::Array(vec![
+            Hocon::Array(vec![
+                Hocon::String(String::from("a")),
+                Hocon::String(String::from("b")),
+            ]),
+            Hocon::Array(vec![Hocon::String(String::from("c"))]),
+            Hocon::String(String::from("d")),
+        ]);
+        assert_eq!(
+            val.as_flat_string_vec(),
+            Some(vec![
+                String::from("a"),
+                String::from("b"),
+                String::from("c"),
+                String::from("d")
+            ])
+        );
+
+        let val = Hocon::Array(vec![
+            Hocon::Array(vec![Hocon::String(String::from("a"))]),
+            Hocon::Array(vec![Hocon::Integer(5)]),
+        ]);
+        assert_eq!(val.as_flat_string_vec(), None);
+
+        let val = Hocon::Array(vec![
+            Hocon::Array(vec![Hocon::String(String::from("a"))]),
+            Hocon::Array(vec![Hocon::Hash(LinkedHashMap::new())]),
+        ]);
+        assert_eq!(val.as_flat_string_vec(), None);
+        assert_eq!(Hocon::Array(vec![Hocon::Null]).as_flat_string_vec(), None);
+
+        assert_eq!(Hocon::String(String::from("a")).as_flat_string_vec(), None);
+    }
+
+    #[test]
+    fn access_as_version() {
+        let version = |s: &str| Hocon::String(String::from(s)).as_version();
+        assert_eq!(version("1.2.3"), Some((1, 2, 3)));
+        assert_eq!(version("10.0.25"), Some((10, 0, 25)));
+        assert_eq!(version("1.2.3-beta.1+build.5"), Some((1, 2, 3)));
+        assert_eq!(version("1.2.3+build"), Some((1, 2, 3)));
+        assert_eq!(version("1.2"), None);
+        assert_eq!(version("1.2.3.4"), None);
+        assert_eq!(version("v1.2.3"), None);
+        assert_eq!(version("1..3"), None);
+        assert_eq!(version("latest"), None);
+        assert_eq!(Hocon::Real(1.2).as_version(), None);
+    }
+
+    #[test]
+    fn access_as_joined_strings() {
+        let val = Hocon::Array(vec![
+            Hocon::String(String::from("a")),
+            Hocon::String(String::from("b")),
+            Hocon::String(String::from("c")),
+        ]);
+        assert_eq!(val.join_strings(", "), Some(String::from("a, b, c")));
+
+        let val = Hocon::Array(vec![Hocon::String(String::from("a")), Hocon::Integer(5)]);
+        assert_eq!(val.join_strings("-"), Some(String::from("a-5")));
+        assert_eq!(Hocon::Array(vec![]).join_strings(", "), Some(String::new()));
+
+        let val = Hocon::Array(vec![Hocon::String(String::from("a")), Hocon::Null]);
+        assert_eq!(val.join_strings(", "), None);
+        assert_eq!(Hocon::String(String::from("a")).join_strings(", "), None);
+    }
+
+    #[test]
+    fn access_as_char_array() {
+        let val = Hocon::Array(vec![
+            Hocon::String(String::from("a")),
+            Hocon::String(String::from("b")),
+        ]);
+        assert_eq!(val.as_char_array(), Some(vec!['a', 'b']));
+        assert_eq!(Hocon::Array(vec![]).as_char_array(), Some(vec![]));
+
+        let val = Hocon::Array(vec![
+            Hocon::String(String::from("a")),
+            Hocon::String(String::from("bc")),
+        ]);
+        assert_eq!(val.as_char_array(), None);
+        let val = Hocon::Array(vec![Hocon::String(String::new())]);
+        assert_eq!(val.as_char_array(), None);
+        assert_eq!(Hocon::Array(vec![Hocon::Integer(1)]).as_char_array(), None);
+        assert_eq!(Hocon::String(String::from("a")).as_char_array(), None);
+    }
+
+    #[test]
+    fn access_as_array_coerced() {
+        let val = Hocon::Array(vec![Hocon::Integer(1), Hocon::Integer(2)]);
+        assert_eq!(
+            val.as_array_coerced(),
+            vec![&Hocon::Integer(1), &Hocon::Integer(2)]
+        );
+        assert!(Hocon::Array(vec![]).as_array_coerced().is_empty());
+
+        let val = Hocon::String(String::from("foo"));
+        assert_eq!(val.as_array_coerced(), vec![&val]);
+        let val = Hocon::Hash(LinkedHashMap::new());
+        assert_eq!(val.as_array_coerced(), vec![&val]);
+
+        assert!(Hocon::Null.as_array_coerced().is_empty());
+        assert!(Hocon::BadValue(crate::Error::MissingKey)
+            .as_array_coerced()
+            .is_empty());
+    }
+
+    #[test]
+    fn access_as_time_of_day_seconds() {
+        let time = |s: &str| Hocon::String(String::from(s)).as_time_of_day_seconds();
+        assert_eq!(time("14:30"), Some(14 * 3600 + 30 * 60));
+        assert_eq!(time("14:30:15"), Some(14 * 3600 + 30 * 60 + 15));
+        assert_eq!(time("00:00"), Some(0));
+        assert_eq!(time("23:59:59"), Some(86399));
+
+        assert_eq!(time("25:00"), None);
+        assert_eq!(time("14:60"), None);
+        assert_eq!(time("14:30:60"), None);
+        assert_eq!(time("4:30"), None);
+        assert_eq!(time("14:30:15:00"), None);
+        assert_eq!(time("14"), None);
+        assert_eq!(Hocon::Integer(1430).as_time_of_day_seconds(), None);
+    }
+
+    #[test]
+    fn access_at_path() {
+        let doc = crate::HoconLoader::new()
+            .load_str(r#"{ a { b { c = 1 }, d = [x, { e = 2 }] }, "0" { f = 3 } }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        assert_eq!(doc.at_path("a.b.c"), &Hocon::Integer(1));
+        assert_eq!(doc.at_path("a.d.0"), &Hocon::String(String::from("x")));
+        assert_eq!(doc.at_path("a.d.1.e"), &Hocon::Integer(2));
+        assert_eq!(doc.at_path("0.f"), &Hocon::Integer(3));
+        assert_eq!(doc.at_path("a"), &doc["a"]);
+
+        assert_eq!(doc.at_path("a.missing.c"), &NOT_FOUND);
+        assert_eq!(doc.at_path("a.d.2.e"), &NOT_FOUND);
+        assert_eq!(doc.at_path("a.b.c.d"), &INVALID_KEY);
+    }
+
+    #[test]
+    fn access_len() {
+        let val = Hocon::Hash(LinkedHashMap::new());
+        assert_eq!(val.len(), Some(0));
+        assert_eq!(val.is_empty(), Some(true));
+
+        let val = Hocon::Array(vec![Hocon::Integer(1), Hocon::Integer(2)]);
+        assert_eq!(val.len(), Some(2));
+        assert_eq!(val.is_empty(), Some(false));
+
+        let val = Hocon::String(String::from("abc"));
+        assert_eq!(val.len(), None);
+        assert_eq!(val.is_empty(), None);
+        assert_eq!(Hocon::BadValue(crate::Error::MissingKey).len(), None);
+    }
+
+    #[test]
+    fn access_contains_path() {
+        let doc = crate::HoconLoader::new()
+            .load_str(r#"{ a { n = null, b { c = 1 }, d = [x] } }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        assert!(doc.contains_path("a.b.c"));
+        assert!(doc.contains_path("a.b"));
+        assert!(doc.contains_path("a.d.0"));
+        assert_eq!(doc["a"]["n"], Hocon::Null);
+        assert!(doc.contains_path("a.n"));
+
+        assert!(!doc.contains_path("a.b.missing"));
+        assert!(!doc.contains_path("a.missing.c"));
+        assert!(!doc.contains_path("a.d.1"));
+        assert!(!doc.contains_path("a.b.c.d"));
+    }
+
     #[test]
     fn access_on_bytes() {
         let val = Hocon::Array(vec![
@@ -749,6 +3246,46 @@ mod tests {
         assert_eq!(val[6].as_bytes(), None);
     }
 
+    #[test]
+    fn access_on_bytes_u64() {
+        let val = Hocon::Array(vec![
+            Hocon::Integer(5),
+            Hocon::String(String::from("1.5KiB")),
+            Hocon::String(String::from("8EiB")),
+            Hocon::String(String::from("18446744073709551614B")),
+            Hocon::String(String::from("16EiB")),
+            Hocon::String(String::from("15.99 EiB")),
+            Hocon::String(String::from("-1kB")),
+            Hocon::Integer(-1),
+            Hocon::String(String::from("5unit")),
+        ]);
+
+        assert_eq!(val[0].as_bytes_u64(), Some(5));
+        assert_eq!(val[1].as_bytes_u64(), Some(1536));
+        assert_eq!(val[2].as_bytes_u64(), Some(2u64.pow(63)));
+        assert_eq!(val[3].as_bytes_u64(), Some(u64::MAX - 1));
+        assert_eq!(val[4].as_bytes_u64(), None);
+        assert_eq!(
+            val[5].as_bytes_u64(),
+            Some((15.99 * 2.0f64.powi(60)).round() as u64)
+        );
+        assert_eq!(val[6].as_bytes_u64(), None);
+        assert_eq!(val[7].as_bytes_u64(), None);
+        assert_eq!(val[8].as_bytes_u64(), None);
+    }
+
+    #[test]
+    fn parse_unit_strings() {
+        assert_eq!(Hocon::str_as_bytes("1.5KiB"), Some(1536));
+        assert_eq!(Hocon::str_as_bytes("10"), Some(10));
+        assert_eq!(Hocon::str_as_bytes("-1kB"), None);
+        assert_eq!(Hocon::str_as_bytes("5unit"), None);
+
+        assert_eq!(Hocon::str_as_milliseconds("2s"), Some(2000.0));
+        assert_eq!(Hocon::str_as_milliseconds("10"), Some(10.0));
+        assert_eq!(Hocon::str_as_milliseconds("5unit"), None);
+    }
+
     #[test]
     fn access_on_bytes_all_bytes_units() {
         for unit in vec!["B", "b", "byte", "bytes"] {
@@ -891,4 +3428,72 @@ mod tests {
             Some(std::time::Duration::from_secs(60 * 60 * 24 * 365))
         );
     }
+
+    #[test]
+    fn access_on_negative_duration() {
+        assert_eq!(Hocon::String(String::from("-1s")).as_duration(), None);
+        assert_eq!(Hocon::Integer(-1).as_duration(), None);
+        assert_eq!(
+            Hocon::String(String::from("0s")).as_duration(),
+            Some(std::time::Duration::from_secs(0))
+        );
+    }
+
+    #[test]
+    fn access_on_saturating_duration() {
+        assert_eq!(
+            Hocon::String(String::from("1.5h")).as_duration_saturating(),
+            Some(std::time::Duration::from_secs(5400))
+        );
+        let years = Hocon::String(String::from("99999 years"));
+        assert_eq!(
+            years.as_duration_saturating(),
+            Some(std::time::Duration::from_secs(99999 * 365 * 24 * 60 * 60))
+        );
+        assert!(years.as_duration() < years.as_duration_saturating());
+        assert_eq!(
+            Hocon::String(String::from("1e20 years")).as_duration_saturating(),
+            Some(std::time::Duration::MAX)
+        );
+        assert_eq!(
+            Hocon::String(String::from("-1s")).as_duration_saturating(),
+            None
+        );
+    }
+
+    #[test]
+    fn access_on_negative_bytes() {
+        assert_eq!(Hocon::String(String::from("-1KiB")).as_bytes(), None);
+        assert_eq!(Hocon::String(String::from("-5")).as_bytes(), None);
+        assert_eq!(Hocon::Integer(-1).as_bytes(), None);
+    }
+
+    #[test]
+    fn access_on_scientific_notation_with_unit() {
+        assert_eq!(
+            Hocon::String(String::from("1e3ms")).as_milliseconds(),
+            Some(1000.0)
+        );
+        assert_eq!(
+            Hocon::String(String::from("1.5e2 KiB")).as_bytes(),
+            Some(153_600)
+        );
+        assert_eq!(
+            Hocon::String(String::from("2E-1 seconds")).as_milliseconds(),
+            Some(200.0)
+        );
+        assert_eq!(Hocon::String(String::from("1e3")).as_bytes(), Some(1000));
+        assert_eq!(
+            Hocon::String(String::from("1E")).as_bytes(),
+            Some(2u64.pow(60))
+        );
+        assert_eq!(
+            Hocon::String(String::from("2e")).as_bytes(),
+            Some(2 * 2u64.pow(60))
+        );
+        assert_eq!(
+            Hocon::String(String::from("1 EB")).as_bytes(),
+            Some(10u64.pow(18))
+        );
+    }
 }
@@ -0,0 +1,36 @@
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+
+use crate::{Error, Hocon};
+
+/// Updated documents, loaded again each time a file watched with
+/// [`HoconLoader::watch`](struct.HoconLoader.html#method.watch) changes. Watching stops when
+/// this is dropped
+#[derive(Debug)]
+pub struct HoconChanges {
+    pub(crate) receiver: Receiver<Result<Hocon, Error>>,
+    // files are watched until the watcher is dropped, which also ends the thread loading
+    // the documents again
+    pub(crate) _watcher: notify::RecommendedWatcher,
+}
+
+impl HoconChanges {
+    /// Get the next updated document if one is available, without waiting
+    pub fn try_recv(&self) -> Option<Result<Hocon, Error>> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Wait for the next updated document, for at most `timeout`
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Result<Hocon, Error>> {
+        self.receiver.recv_timeout(timeout).ok()
+    }
+}
+
+impl Iterator for HoconChanges {
+    type Item = Result<Hocon, Error>;
+
+    /// Wait for the next updated document
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
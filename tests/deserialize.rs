@@ -143,6 +143,58 @@ fn deserialize_struct_duration_with() {
     assert_eq!(doc.a, std::time::Duration::from_secs(1));
 }
 
+#[test]
+fn deserialize_struct_durations_with() {
+    use hocon::de::wrappers::Serde;
+    use std::time::Duration;
+    #[derive(Deserialize, Debug)]
+    struct Test {
+        #[serde(deserialize_with = "Serde::<Vec<Duration>>::with")]
+        retries: Vec<Duration>,
+        wrapped: Serde<Vec<Duration>>,
+    }
+
+    let s = r#"{ retries = [1s, 2s, 500ms], wrapped = ["1 minute"] }"#;
+
+    let doc: Test = dbg!(hocon::de::from_str(s)).expect("during test");
+
+    assert_eq!(
+        doc.retries,
+        vec![
+            Duration::from_secs(1),
+            Duration::from_secs(2),
+            Duration::from_millis(500)
+        ]
+    );
+    assert_eq!(*doc.wrapped, vec![Duration::from_secs(60)]);
+
+    let s = r#"{ retries = [1s, never], wrapped = [] }"#;
+    let doc: Result<Test, _> = hocon::de::from_str(s);
+    assert!(doc.is_err());
+}
+
+#[test]
+fn deserialize_struct_byte_size_with() {
+    use hocon::de::wrappers::{ByteSize, Serde};
+    #[derive(Deserialize, Debug)]
+    struct Test {
+        #[serde(deserialize_with = "Serde::<ByteSize>::with")]
+        cache_size: u64,
+        wrapped: Serde<ByteSize>,
+    }
+
+    let s = r#"{ cache_size = 2.5M, wrapped = 1.5KiB }"#;
+
+    let doc: Test = dbg!(hocon::de::from_str(s)).expect("during test");
+
+    assert_eq!(doc.cache_size, 2_621_440);
+    assert_eq!(*doc.wrapped, ByteSize(1536));
+
+    let s = r#"{ cache_size = lots, wrapped = 1 }"#;
+    let doc: Result<Test, _> = hocon::de::from_str(s);
+    assert!(doc.is_err());
+}
+
 #[test]
 fn deserialize_filesize() {
     #[derive(Deserialize, Debug)]
@@ -170,3 +222,115 @@ fn deserialize_filesize_as_float() {
     let doc: Test = dbg!(hocon::de::from_str(s)).expect("during test");
     assert_eq!(doc.data, 2621440.0);
 }
+
+#[test]
+fn deserialize_borrowed_str() {
+    #[derive(Deserialize, Debug)]
+    struct Test<'a> {
+        #[allow(dead_code)]
+        a: &'a str,
+    }
+
+    let s = r#"{"a":"dndjf"}"#;
+    let doc: Result<Test, _> = dbg!(hocon::de::from_str(s));
+
+    assert_eq!(
+        doc.unwrap_err(),
+        hocon::Error::Deserialization {
+            message: String::from(
+                "a: HOCON values can't be borrowed, use String or Cow<str> instead of &str"
+            )
+        }
+    );
+}
+
+#[test]
+fn deserialize_cow_str() {
+    use std::borrow::Cow;
+
+    #[derive(Deserialize, Debug)]
+    struct Test<'a> {
+        a: Cow<'a, str>,
+    }
+
+    let s = r#"{"a":"dndjf"}"#;
+    let doc: Test = dbg!(hocon::de::from_str(s)).expect("during test");
+
+    assert_eq!(doc.a, "dndjf");
+}
+
+#[test]
+fn deserialize_with_strict_types() {
+    #[derive(Deserialize, Debug)]
+    struct Server {
+        port: u16,
+    }
+
+    let s = r#"{ port = "80" }"#;
+    let doc: Server = dbg!(HoconLoader::new()
+        .load_str(s)
+        .expect("during test")
+        .resolve())
+    .expect("during test");
+    assert_eq!(doc.port, 80);
+
+    let res: Result<Server, _> = dbg!(HoconLoader::new()
+        .strict_types()
+        .load_str(s)
+        .expect("during test")
+        .resolve());
+    assert!(res.is_err());
+
+    let doc: Server = dbg!(HoconLoader::new()
+        .strict_types()
+        .load_str("{ port = 80 }")
+        .expect("during test")
+        .resolve())
+    .expect("during test");
+    assert_eq!(doc.port, 80);
+}
+
+#[test]
+fn deserialize_preserved_number_text_as_number() {
+    #[derive(Deserialize, Debug)]
+    struct Test {
+        a: f64,
+    }
+
+    let doc = dbg!(HoconLoader::new()
+        .preserve_number_text()
+        .strict_types()
+        .load_str("{ a = 1.0 }")
+        .expect("during test")
+        .hocon())
+    .expect("during test");
+    assert_eq!(
+        serde_json::to_string(&doc).expect("during test"),
+        r#"{"a":1.0}"#
+    );
+
+    let doc: Test = dbg!(HoconLoader::new()
+        .preserve_number_text()
+        .strict_types()
+        .load_str("{ a = 1e3 }")
+        .expect("during test")
+        .resolve())
+    .expect("during test");
+    assert_eq!(doc.a, 1000.0);
+}
+
+#[test]
+fn deserialize_vec_of_options_with_null() {
+    #[derive(Deserialize, Debug)]
+    struct Test {
+        a: Vec<Option<i64>>,
+    }
+
+    let doc: Test = dbg!(hocon::de::from_str("a = [1, null, 3]")).expect("during test");
+
+    assert_eq!(doc.a, vec![Some(1), None, Some(3)]);
+
+    let doc: Test = dbg!(hocon::de::from_str("a = [null, 2, null]")).expect("during test");
+
+    assert_eq!(doc.a, vec![None, Some(2), None]);
+}
@@ -1,8 +1,34 @@
 use std::fs::File;
 use std::io::prelude::*;
+use std::path::{Path, PathBuf};
 
 test_generator::test_expand_paths! { file_load; "tests/data/*.conf" }
 
+// A directory for the files written by a test, removed when dropped even if the test fails
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("hocon-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).expect("during test");
+        TempDir(dir)
+    }
+}
+
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
 fn file_load(file_name: &str) {
     let doc = hocon::HoconLoader::new()
         .no_system()
@@ -24,3 +50,237 @@ fn missing_file() {
 
     assert!(dbg!(doc).is_err());
 }
+
+#[test]
+fn load_file_from_stdin() {
+    // the test runs itself again in a child process, with the document on its standard input
+    if std::env::var_os("HOCON_TEST_STDIN").is_some() {
+        let doc = hocon::HoconLoader::new()
+            .load_file("-")
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["a"], hocon::Hocon::Integer(7));
+        return;
+    }
+
+    let mut child = std::process::Command::new(std::env::current_exe().expect("during test"))
+        .args(&["load_file_from_stdin", "--exact"])
+        .env("HOCON_TEST_STDIN", "1")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .expect("during test");
+    child
+        .stdin
+        .take()
+        .expect("during test")
+        .write_all(b"{ a: 7 }")
+        .expect("during test");
+
+    assert!(child.wait().expect("during test").success());
+}
+
+#[cfg(feature = "serde-support")]
+#[test]
+fn layer_json_value_and_hocon() {
+    let value = serde_json::json!({
+        "a": 1,
+        "b": { "c": "from json", "d": [1, 2], "e": null },
+        "f.g": true,
+    });
+
+    let doc = hocon::HoconLoader::new()
+        .load_json_value(&value)
+        .expect("during test")
+        .load_str("a = 2, b.c = from hocon")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["a"].as_i64(), Some(2));
+    assert_eq!(doc["b"]["c"].as_string(), Some(String::from("from hocon")));
+    assert_eq!(
+        doc["b"]["d"],
+        hocon::Hocon::Array(vec![hocon::Hocon::Integer(1), hocon::Hocon::Integer(2)])
+    );
+    assert_eq!(doc["b"]["e"], hocon::Hocon::Null);
+    assert_eq!(doc["f.g"].as_bool(), Some(true));
+
+    let doc = hocon::HoconLoader::new()
+        .load_str("a = 2, b.c = from hocon, h = 1.5")
+        .expect("during test")
+        .load_json_value(&value)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["a"].as_i64(), Some(1));
+    assert_eq!(doc["b"]["c"].as_string(), Some(String::from("from json")));
+    assert_eq!(doc["h"].as_f64(), Some(1.5));
+}
+
+#[test]
+fn reload_with_shared_cache() {
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    let dir = TempDir::new("shared-cache");
+    let main = dir.join("main.conf");
+    let included = dir.join("included.conf");
+    std::fs::write(&main, "include \"included.conf\"\nb = 2").expect("during test");
+    let write_included = |content: &str, modified: SystemTime| {
+        std::fs::write(&included, content).expect("during test");
+        File::options()
+            .write(true)
+            .open(&included)
+            .and_then(|file| file.set_modified(modified))
+            .expect("during test");
+    };
+    let load = |cache: &Arc<hocon::FileCache>| {
+        hocon::HoconLoader::new()
+            .with_shared_cache(cache.clone())
+            .load_file(&main)
+            .expect("during test")
+            .hocon()
+            .expect("during test")
+    };
+    let modified = SystemTime::now() - Duration::from_secs(60);
+
+    let cache = Arc::new(hocon::FileCache::new());
+    write_included("a = 1", modified);
+    assert_eq!(load(&cache)["a"].as_i64(), Some(1));
+    assert_eq!(cache.len(), 2);
+
+    // same modification time, the include is served from the cache
+    write_included("a = 3", modified);
+    assert_eq!(load(&cache)["a"].as_i64(), Some(1));
+
+    // changed include is read again
+    write_included("a = 3", modified + Duration::from_secs(1));
+    assert_eq!(load(&cache)["a"].as_i64(), Some(3));
+    assert_eq!(load(&cache)["b"].as_i64(), Some(2));
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn same_include_twice_with_shared_cache() {
+    let dir = TempDir::new("shared-cache-twice");
+    let main = dir.join("main.conf");
+    std::fs::write(dir.join("included.conf"), "a += { x: 1 }").expect("during test");
+    std::fs::write(
+        &main,
+        "include \"included.conf\"\ninclude \"included.conf\"",
+    )
+    .expect("during test");
+
+    let cache = std::sync::Arc::new(hocon::FileCache::new());
+    for _ in 0..2 {
+        let doc = hocon::HoconLoader::new()
+            .with_shared_cache(cache.clone())
+            .load_file(&main)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        assert_eq!(doc["a"][1]["x"].as_i64(), Some(1));
+    }
+}
+
+#[test]
+fn file_without_extension_preferred_file_type() {
+    let dir = TempDir::new("file-types");
+    std::fs::write(
+        dir.join("app.properties"),
+        "all = properties\npj = properties\nph = properties",
+    )
+    .expect("during test");
+    std::fs::write(
+        dir.join("app.json"),
+        r#"{ "all": "json", "pj": "json", "jh": "json" }"#,
+    )
+    .expect("during test");
+    std::fs::write(dir.join("app.conf"), "all = hocon\nph = hocon\njh = hocon")
+        .expect("during test");
+
+    let load = |loader: hocon::HoconLoader| {
+        let doc = loader
+            .load_file(dir.join("app"))
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        ["all", "pj", "ph", "jh"]
+            .iter()
+            .map(|key| doc[*key].as_string().expect("during test"))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        load(hocon::HoconLoader::new()),
+        vec!["hocon", "json", "hocon", "hocon"]
+    );
+    assert_eq!(
+        load(hocon::HoconLoader::new().preferred_file_type(hocon::FileType::Json)),
+        vec!["json", "json", "hocon", "json"]
+    );
+    assert_eq!(
+        load(hocon::HoconLoader::new().preferred_file_type(hocon::FileType::Properties)),
+        vec!["properties", "properties", "properties", "hocon"]
+    );
+}
+
+#[test]
+fn reload_changed_file() {
+    use std::time::{Duration, SystemTime};
+
+    let dir = TempDir::new("reload");
+    let path = dir.join("app.conf");
+    let write = |content: &str, modified: SystemTime| {
+        std::fs::write(&path, content).expect("during test");
+        File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(modified))
+            .expect("during test");
+    };
+    let modified = SystemTime::now() - Duration::from_secs(60);
+    write("a = 1, b = 1", modified);
+
+    let loader = hocon::HoconLoader::new()
+        .load_str("a = 0, c = 0")
+        .expect("during test")
+        .load_file(&path)
+        .expect("during test")
+        .load_str("b = 2")
+        .expect("during test");
+    assert_eq!(loader.reload(), Ok(None));
+
+    write("a = 3, b = 3", modified + Duration::from_secs(1));
+    let doc = loader.reload().expect("during test").expect("during test");
+    assert_eq!(doc["a"].as_i64(), Some(3));
+    assert_eq!(doc["b"].as_i64(), Some(2));
+    assert_eq!(doc["c"].as_i64(), Some(0));
+    assert_eq!(loader.reload(), Ok(None));
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn watch_changed_include() {
+    use std::time::Duration;
+
+    let dir = TempDir::new("watch");
+    std::fs::write(dir.join("app.conf"), "a = 1\ninclude \"included.conf\"").expect("during test");
+    std::fs::write(dir.join("included.conf"), "b = 1").expect("during test");
+
+    let (doc, changes) = hocon::HoconLoader::new()
+        .load_file(dir.join("app.conf"))
+        .expect("during test")
+        .watch()
+        .expect("during test");
+    assert_eq!(doc["b"].as_i64(), Some(1));
+
+    std::fs::write(dir.join("included.conf"), "b = 2").expect("during test");
+    let doc = changes
+        .recv_timeout(Duration::from_secs(10))
+        .expect("during test")
+        .expect("during test");
+    assert_eq!(doc["a"].as_i64(), Some(1));
+    assert_eq!(doc["b"].as_i64(), Some(2));
+}
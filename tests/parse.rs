@@ -1,7 +1,7 @@
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
 
-use hocon::{Error, Hocon, HoconLoader};
+use hocon::{Error, Hocon, HoconLoader, MergeStrategy};
 use linked_hash_map::LinkedHashMap;
 
 #[test]
@@ -218,6 +218,44 @@ fn parse_comment() {
     assert_eq!(doc["a"].as_i64().expect("during test"), 5);
 }
 
+#[test]
+fn parse_comment_after_unquoted_string() {
+    let value = |s: &str| {
+        HoconLoader::new()
+            .load_str(s)
+            .expect("during test")
+            .hocon()
+            .expect("during test")["a"]
+            .clone()
+    };
+
+    assert_eq!(
+        value("a = /usr/local // x"),
+        Hocon::String(String::from("/usr/local"))
+    );
+    // `//` only starts a comment at the start of a value or after a whitespace
+    assert_eq!(value("a = val//c"), Hocon::String(String::from("val//c")));
+    assert_eq!(value("a = b // comment"), Hocon::String(String::from("b")));
+    assert_eq!(value("a = val # c"), Hocon::String(String::from("val")));
+    assert_eq!(
+        value("a = /usr/bin"),
+        Hocon::String(String::from("/usr/bin"))
+    );
+    assert_eq!(value("a = a/b/c"), Hocon::String(String::from("a/b/c")));
+    assert_eq!(
+        value("a = http://x"),
+        Hocon::String(String::from("http://x"))
+    );
+    assert_eq!(
+        value("a = http://x/y // comment"),
+        Hocon::String(String::from("http://x/y"))
+    );
+    assert_eq!(
+        value(r#"a = "http://x""#),
+        Hocon::String(String::from("http://x"))
+    );
+}
+
 #[test]
 fn parse_keyvalue_separator() {
     let s = r#"{"a":5,"b"=6,"c" {"a":1}}}"#;
@@ -464,6 +502,137 @@ fn parse_missing_optional_substitution() {
     assert_eq!(doc["a"]["c"], Hocon::Null);
 }
 
+#[test]
+fn parse_optional_substitution_from_previous_document() {
+    let doc: Hocon = HoconLoader::new()
+        .strict()
+        .load_str(r#"{ a = 1, o { c = [1] } }"#)
+        .expect("during test")
+        .load_str(r#"{ b = ${?a}, x { b = ${?a} }, o { c = ${?o.c} [2] }, d = ${?missing} }"#)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(doc["b"], Hocon::Integer(1));
+    assert_eq!(doc["x"]["b"], Hocon::Integer(1));
+    assert_eq!(
+        doc["o"]["c"],
+        Hocon::Array(vec![Hocon::Integer(1), Hocon::Integer(2)])
+    );
+    assert_eq!(doc["d"], Hocon::Null);
+}
+
+#[test]
+fn parse_substitution_with_inline_default() {
+    let doc: Hocon = HoconLoader::new()
+        .strict()
+        .no_system()
+        .load_str(
+            r#"{
+                a = 1, x { y = 5 }
+                resolved = ${a:-2}
+                defaulted = ${missing:-fallback value}
+                nested_path = ${x.y:-0}
+                nested_default = ${missing:-${x.y}}
+                deep_default = ${missing:-${other:-deep}}
+                optional = ${?missing:-opt}
+                in_string = "port "${missing:-80}
+            }"#,
+        )
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(doc["resolved"], Hocon::Integer(1));
+    assert_eq!(
+        doc["defaulted"].as_string(),
+        Some(String::from("fallback value"))
+    );
+    assert_eq!(doc["nested_path"], Hocon::Integer(5));
+    assert_eq!(doc["nested_default"], Hocon::Integer(5));
+    assert_eq!(doc["deep_default"].as_string(), Some(String::from("deep")));
+    assert_eq!(doc["optional"].as_string(), Some(String::from("opt")));
+    assert_eq!(doc["in_string"].as_string(), Some(String::from("port 80")));
+}
+
+#[test]
+fn parse_substitution_cycles() {
+    for (s, keys) in &[
+        ("a = ${b}, b = ${a}", vec!["a", "b"]),
+        ("a = ${b}, b = ${c}, c = ${a}", vec!["a", "b", "c"]),
+    ] {
+        let doc: Hocon = HoconLoader::new()
+            .no_system()
+            .load_str(s)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+        for key in keys {
+            assert!(matches!(
+                doc[*key],
+                Hocon::BadValue(Error::SubstitutionCycle { .. })
+            ));
+        }
+
+        let doc = HoconLoader::new()
+            .strict()
+            .no_system()
+            .load_str(s)
+            .and_then(|loader| loader.hocon());
+        assert!(matches!(doc, Err(Error::SubstitutionCycle { .. })));
+    }
+
+    let chain = "a = ${b}, b = ${c}, c = ${d}, d = 1";
+    let doc: Hocon = HoconLoader::new()
+        .strict()
+        .load_str(chain)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["a"], Hocon::Integer(1));
+    let doc = HoconLoader::new()
+        .strict()
+        .max_substitution_depth(2)
+        .load_str(chain)
+        .and_then(|loader| loader.hocon());
+    assert!(matches!(doc, Err(Error::SubstitutionCycle { .. })));
+}
+
+#[test]
+fn parse_with_pending_substitutions() {
+    let loader = HoconLoader::new()
+        .strict()
+        .no_system()
+        .load_str(
+            r#"{
+                a = 1
+                resolved = ${a}
+                pending = ${b.c}
+                in_string = "value "${b.c}
+            }"#,
+        )
+        .expect("during test");
+
+    let (doc, pending) = loader.clone().pending_substitutions();
+    assert_eq!(doc["resolved"], Hocon::Integer(1));
+    assert_eq!(
+        doc["pending"],
+        Hocon::BadValue(Error::KeyNotFound {
+            key: String::from("b.c")
+        })
+    );
+    assert_eq!(doc["in_string"], doc["pending"]);
+    assert_eq!(pending, vec![String::from("b.c")]);
+
+    let doc: Hocon = loader
+        .load_str("b.c = 2")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["pending"], Hocon::Integer(2));
+    assert_eq!(doc["in_string"].as_string(), Some(String::from("value 2")));
+}
+
 #[test]
 fn parse_empty_object() {
     let s = r#"a=[{},{}],b=[]"#;
@@ -948,6 +1117,154 @@ fn parse_concat_arrays_with_plus_equal_with_object() {
     assert_eq!(doc["a"][3]["f"]["g"].as_i64().expect("during test"), 6);
 }
 
+#[test]
+fn parse_concat_arrays_with_plus_equal_without_init() {
+    let doc: Hocon = HoconLoader::new()
+        .strict()
+        .load_str("a += 1")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(doc["a"], Hocon::Array(vec![Hocon::Integer(1)]));
+}
+
+#[test]
+fn parse_concat_arrays_with_plus_equal_from_several_documents() {
+    let doc: Hocon = HoconLoader::new()
+        .strict()
+        .load_str("a += { b : 1, c : 2 }")
+        .expect("during test")
+        .load_str("a += { b : 3 }")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(doc["a"].as_array_coerced().len(), 2);
+    assert_eq!(doc["a"][0]["b"], Hocon::Integer(1));
+    assert_eq!(doc["a"][0]["c"], Hocon::Integer(2));
+    assert_eq!(doc["a"][1]["b"], Hocon::Integer(3));
+}
+
+#[test]
+fn parse_concat_arrays_with_plus_equal_on_scalar() {
+    let s = r#"{
+        a = 5
+        a += 2
+    }"#;
+    let doc: Hocon = HoconLoader::new()
+        .load_str(s)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(
+        doc["a"],
+        Hocon::BadValue(Error::TypeMismatch {
+            expected: String::from("an array to concatenate to")
+        })
+    );
+
+    let doc = HoconLoader::new()
+        .strict()
+        .load_str(s)
+        .and_then(|loader| loader.hocon());
+    assert_eq!(
+        doc,
+        Err(Error::TypeMismatch {
+            expected: String::from("an array to concatenate to")
+        })
+    );
+}
+
+#[test]
+fn parse_concat_arrays_with_plus_equal_across_includes() {
+    let doc: Hocon = HoconLoader::new()
+        .load_file("tests/data/include_plus_equal.conf")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(
+        doc["a"],
+        Hocon::Array(vec![
+            Hocon::Integer(1),
+            Hocon::Integer(2),
+            Hocon::Integer(3),
+            Hocon::Integer(4)
+        ])
+    );
+    assert_eq!(
+        doc["b"],
+        Hocon::Array(vec![Hocon::Integer(1), Hocon::Integer(0)])
+    );
+}
+
+#[test]
+fn parse_concat_arrays_with_plus_equal_to_array_across_includes() {
+    let doc: Hocon = HoconLoader::new()
+        .load_file("tests/data/include_plus_equal_base.conf")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(
+        doc["a"],
+        Hocon::Array(vec![Hocon::Integer(1), Hocon::Integer(2)])
+    );
+    assert_eq!(
+        doc["x"]["a"],
+        Hocon::Array(vec![
+            Hocon::Integer(1),
+            Hocon::Integer(2),
+            Hocon::Integer(3)
+        ])
+    );
+    assert_eq!(doc["x"]["b"], Hocon::Array(vec![Hocon::Integer(1)]));
+}
+
+#[test]
+fn parse_concat_arrays_with_plus_equal_to_included_array() {
+    let doc: Hocon = HoconLoader::new()
+        .load_file("tests/data/include_plus_equal_list.conf")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    let expected = Hocon::Array(vec![
+        Hocon::Integer(1),
+        Hocon::Integer(2),
+        Hocon::Integer(3),
+    ]);
+    assert_eq!(doc["list"], expected);
+    assert_eq!(doc["substituted"], expected);
+}
+
+#[test]
+fn parse_keeps_keys_in_document_order() {
+    let doc: Hocon = HoconLoader::new()
+        .load_file("tests/data/test01.conf")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    let keys = |value: &Hocon| {
+        value
+            .entries()
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(keys(&doc), vec!["strings", "arrays"]);
+    assert_eq!(keys(&doc["strings"]), vec!["new-a", "new-ab", "a", "b"]);
+
+    let doc: Hocon = HoconLoader::new()
+        .load_str("zeta = 1\nalpha { z = 1, a = 2 }\nmid = 3\nalpha.b = 4\nzeta = 5")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(keys(&doc), vec!["zeta", "alpha", "mid"]);
+    assert_eq!(keys(&doc["alpha"]), vec!["z", "a", "b"]);
+}
+
 #[test]
 fn parse_null_value() {
     let s = r#"{
@@ -993,3 +1310,505 @@ fn hyphen_keys() {
         Hocon::Boolean(true)
     );
 }
+
+#[test]
+fn parse_preserving_number_text() {
+    let s = r#"{ a = 1.0, b = 1e3, c = 2, d = 2.5, f = [1.50], e = 1.0.0 }"#;
+    let loader = dbg!(HoconLoader::new().preserve_number_text().load_str(s)).expect("during test");
+    let doc = loader.clone().hocon().expect("during test");
+
+    assert_eq!(doc["a"], Hocon::Real(1.0));
+    assert_eq!(doc["b"], Hocon::Real(1000.0));
+    assert_eq!(doc["b"].to_string(), "1000");
+    assert_eq!(doc["c"], Hocon::Integer(2));
+    assert_eq!(doc["e"].as_string(), Some(String::from("1.0.0")));
+
+    let number_texts = loader.number_texts().expect("during test");
+    assert_eq!(number_texts["a"], "1.0");
+    assert_eq!(number_texts["b"], "1e3");
+    assert_eq!(number_texts["d"], "2.5");
+    assert_eq!(number_texts["f.0"], "1.50");
+    assert!(!number_texts.contains_key("c"));
+    assert!(!number_texts.contains_key("e"));
+
+    let round_trip = doc.to_hocon_string_with_number_texts(&number_texts);
+    assert_eq!(round_trip, r#"{a:1.0,b:1e3,c:2,d:2.5,f:[1.50],e:"1.0.0"}"#);
+    let reloaded = dbg!(HoconLoader::new().load_str(&round_trip))
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(reloaded, doc);
+
+    // without the option, no text is kept
+    let number_texts = dbg!(HoconLoader::new().load_str(s))
+        .expect("during test")
+        .number_texts()
+        .expect("during test");
+    assert!(number_texts.is_empty());
+}
+
+#[test]
+fn parse_preserving_number_text_of_overridden_values() {
+    let number_texts = dbg!(HoconLoader::new()
+        .preserve_number_text()
+        .load_str("{ a = 1.0, b = 2.0, c { d = 3.0 } }"))
+    .expect("during test")
+    .load_str("{ a = 1, b = 2.50, c = 3.00 }")
+    .expect("during test")
+    .number_texts()
+    .expect("during test");
+
+    assert!(!number_texts.contains_key("a"));
+    assert_eq!(number_texts["b"], "2.50");
+    assert_eq!(number_texts["c"], "3.00");
+    assert!(!number_texts.contains_key("c.d"));
+}
+
+#[test]
+fn parse_include_with_alias() {
+    let doc: Hocon = dbg!(HoconLoader::new()
+        .strict()
+        .load_file("tests/data/include_alias.conf")
+        .expect("during test")
+        .hocon())
+    .expect("during test");
+
+    assert_eq!(doc["shared"]["f"]["g"], Hocon::Boolean(false));
+    assert_eq!(doc["a"], Hocon::Integer(5));
+    assert_eq!(
+        doc["db"]["defaults"]["e"].as_string(),
+        Some(String::from("val"))
+    );
+    assert_eq!(doc["db"]["port"], Hocon::Integer(80));
+    assert_eq!(doc["f"], Hocon::BadValue(hocon::Error::MissingKey));
+}
+
+#[test]
+fn parse_many_references_to_same_object() {
+    let s = r#"{
+        first = ${shared}
+        second = ${shared}
+        shared { a = 1, b = [x, y], c { d = ${shared.a} } }
+        third = ${shared} { e = 2 }
+        nested { again = ${shared.c} }
+    }"#;
+    let doc: Hocon = dbg!(HoconLoader::new().load_str(s))
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(doc["first"], doc["shared"]);
+    assert_eq!(doc["second"], doc["shared"]);
+    assert_eq!(doc["shared"]["c"]["d"], Hocon::Integer(1));
+    assert_eq!(doc["third"]["a"], Hocon::Integer(1));
+    assert_eq!(doc["third"]["e"], Hocon::Integer(2));
+    assert_eq!(doc["nested"]["again"], doc["shared"]["c"]);
+}
+
+#[test]
+fn parse_self_references_with_cached_substitutions() {
+    let s = r#"{
+        a { b = 1, c = ${a} }
+        d = ${a}
+        e = ${a.c}
+        x = 1
+        x = ${x}" "${x}
+        y = ${x}
+        z { w = ${y}, v = ${x} }
+    }"#;
+    let doc: Hocon = dbg!(HoconLoader::new().load_str(s))
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(doc["a"]["c"]["b"], Hocon::Integer(1));
+    assert_eq!(
+        doc["a"]["c"]["c"],
+        Hocon::BadValue(hocon::Error::MissingKey)
+    );
+    assert_eq!(doc["d"], doc["a"]);
+    assert_eq!(doc["e"], doc["a"]["c"]);
+    assert_eq!(doc["x"].as_string(), Some(String::from("1 1")));
+    assert_eq!(doc["y"], doc["x"]);
+    assert_eq!(doc["z"]["w"], doc["x"]);
+    assert_eq!(doc["z"]["v"], doc["x"]);
+}
+
+#[test]
+fn parse_scientific_notation_with_units() {
+    let s = r#"{ duration = 1e3ms, size = 1.5e2 KiB }"#;
+    let doc: Hocon = dbg!(HoconLoader::new().load_str(s))
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(doc["duration"].as_milliseconds(), Some(1000.0));
+    assert_eq!(doc["size"].as_bytes(), Some(153_600));
+}
+
+#[test]
+fn build_with_macro() {
+    let built = hocon::hocon!({
+        "a": 5,
+        "b": 6.7,
+        "c": [1, 2, 3],
+        "d": true,
+        "e": "val",
+        "f": { "g": false, "h": null, "i": (-1) },
+        "j": [],
+        "k": {}
+    });
+    let s = r#"{ a: 5, b: 6.7, c: [1, 2, 3], d: true, e: val, f { g: false, h: null, i: -1 }, j: [], k: {} }"#;
+    let parsed: Hocon = dbg!(HoconLoader::new().load_str(s))
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    assert_eq!(built, parsed);
+}
+
+#[test]
+fn round_trip_through_hocon_text() {
+    for file in &[
+        "tests/data/basic.conf",
+        "tests/data/concatenation.conf",
+        "tests/data/hyphen-key.conf",
+        "tests/data/test01.conf",
+    ] {
+        let doc: Hocon = HoconLoader::new()
+            .no_system()
+            .load_file(file)
+            .expect("during test")
+            .hocon()
+            .expect("during test");
+
+        for rendered in &[doc.to_string_pretty(), doc.to_hocon_string()] {
+            let reloaded: Hocon = dbg!(HoconLoader::new().load_str(dbg!(rendered)))
+                .expect("during test")
+                .hocon()
+                .expect("during test");
+            assert_eq!(reloaded, doc);
+        }
+    }
+
+    let s = r#"{ "quote\"d": "line\nbreak", "": " padded ", unicode: "é😀", real: 1.0, neg: -5 }"#;
+    let doc: Hocon = HoconLoader::new()
+        .load_str(s)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    let reloaded: Hocon = dbg!(HoconLoader::new().load_str(dbg!(&doc.to_string_pretty())))
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(reloaded, doc);
+}
+
+#[test]
+fn parse_with_merge_strategies() {
+    let s = r#"{ a { x = 1, l = [1] }, a { y = 2, l = [2, 3] }, b = [{ z: 1 }], b = [{ z: 2 }] }"#;
+    let load = |strategy| {
+        HoconLoader::new()
+            .merge_strategy(strategy)
+            .load_str(s)
+            .expect("during test")
+            .hocon()
+            .expect("during test")
+    };
+
+    let doc = load(MergeStrategy::ObjectMerge);
+    assert_eq!(doc["a"]["x"].as_i64(), Some(1));
+    assert_eq!(doc["a"]["y"].as_i64(), Some(2));
+    assert_eq!(
+        doc["a"]["l"],
+        Hocon::Array(vec![Hocon::Integer(2), Hocon::Integer(3)])
+    );
+    assert_eq!(doc["b"], Hocon::Array(vec![hocon::hocon!({ "z": 2 })]));
+
+    let doc = load(MergeStrategy::ReplaceAll);
+    assert_eq!(doc["a"]["x"], Hocon::BadValue(Error::MissingKey));
+    assert_eq!(doc["a"]["y"].as_i64(), Some(2));
+    assert_eq!(
+        doc["a"]["l"],
+        Hocon::Array(vec![Hocon::Integer(2), Hocon::Integer(3)])
+    );
+    assert_eq!(doc["b"], Hocon::Array(vec![hocon::hocon!({ "z": 2 })]));
+
+    let doc = load(MergeStrategy::ConcatArrays);
+    assert_eq!(doc["a"]["x"].as_i64(), Some(1));
+    assert_eq!(doc["a"]["y"].as_i64(), Some(2));
+    assert_eq!(
+        doc["a"]["l"],
+        Hocon::Array(vec![
+            Hocon::Integer(1),
+            Hocon::Integer(2),
+            Hocon::Integer(3)
+        ])
+    );
+    assert_eq!(
+        doc["b"],
+        Hocon::Array(vec![hocon::hocon!({ "z": 1 }), hocon::hocon!({ "z": 2 })])
+    );
+}
+
+#[test]
+fn parse_with_merge_strategies_across_documents() {
+    let load = |strategy| {
+        HoconLoader::new()
+            .merge_strategy(strategy)
+            .load_str(r#"{ a { x = 1, b { z = 1 } }, c = 3 }"#)
+            .expect("during test")
+            .load_str(r#"{ a { y = 2 }, c { w = 4 } }"#)
+            .expect("during test")
+            .hocon()
+            .expect("during test")
+    };
+
+    let doc = load(MergeStrategy::ObjectMerge);
+    assert_eq!(doc["a"]["x"].as_i64(), Some(1));
+    assert_eq!(doc["a"]["b"]["z"].as_i64(), Some(1));
+    assert_eq!(doc["a"]["y"].as_i64(), Some(2));
+    assert_eq!(doc["c"]["w"].as_i64(), Some(4));
+
+    let doc = load(MergeStrategy::ReplaceAll);
+    assert_eq!(doc["a"]["x"], Hocon::BadValue(Error::MissingKey));
+    assert_eq!(doc["a"]["b"], Hocon::BadValue(Error::MissingKey));
+    assert_eq!(doc["a"]["y"].as_i64(), Some(2));
+    assert_eq!(doc["c"]["w"].as_i64(), Some(4));
+}
+
+#[test]
+fn round_trip_through_ascii_safe_text() {
+    let s = r#"{ "emoji😀": "smile 😀", bmp: "中文 é", plain: text }"#;
+    let doc: Hocon = HoconLoader::new()
+        .load_str(s)
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+
+    let rendered = doc.to_ascii_safe_string();
+    assert!(rendered.is_ascii());
+    assert_eq!(
+        rendered,
+        r#"{"emoji\ud83d\ude00":"smile \ud83d\ude00",bmp:"\u4e2d\u6587 \u00e9",plain:text}"#
+    );
+
+    let reloaded: Hocon = dbg!(HoconLoader::new().load_str(dbg!(&rendered)))
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(reloaded, doc);
+}
+
+#[test]
+fn parse_unquoted_string_with_allowed_char() {
+    let s = r#"user = me@example"#;
+    let doc: Hocon = dbg!(HoconLoader::new()
+        .unquoted_string_allow("@")
+        .load_str(dbg!(s)))
+    .expect("during test")
+    .hocon()
+    .expect("during test");
+    assert_eq!(doc["user"].as_string(), Some(String::from("me@example")));
+
+    let doc = HoconLoader::new().load_str(s).and_then(HoconLoader::hocon);
+    assert_ne!(
+        doc.map(|doc| doc["user"].as_string()),
+        Ok(Some(String::from("me@example")))
+    );
+}
+
+#[test]
+fn parse_key_value_separated_by_space() {
+    let s = "nameserver 8.8.8.8\nsearch = example.com\noptions {\n  timeout 2\n}\n";
+    let doc: Hocon = dbg!(HoconLoader::new().allow_space_separator().load_str(dbg!(s)))
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["nameserver"].as_string(), Some(String::from("8.8.8.8")));
+    assert_eq!(doc["search"].as_string(), Some(String::from("example.com")));
+    assert_eq!(doc["options"]["timeout"], Hocon::Integer(2));
+
+    let doc = HoconLoader::new()
+        .strict()
+        .load_str("nameserver 8.8.8.8")
+        .map(|_| ());
+    assert!(doc.is_err());
+}
+
+#[test]
+fn parse_error_with_location() {
+    let s = "a = 1\nb {\n  c = 2\n}\n  d = }\n";
+    let doc = dbg!(HoconLoader::new().strict().load_str(dbg!(s)));
+
+    assert_eq!(
+        doc.map(|_| ()),
+        Err(Error::Parse {
+            line: 5,
+            column: 3,
+            snippet: String::from("d = }"),
+        })
+    );
+    assert_eq!(
+        HoconLoader::new()
+            .strict()
+            .load_str(s)
+            .map(|_| ())
+            .unwrap_err()
+            .to_string(),
+        "Parse error at line 5, column 3"
+    );
+
+    // without strict mode, the document is loaded up to where parsing stopped
+    let doc = dbg!(HoconLoader::new().load_str(s))
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["a"], Hocon::Integer(1));
+    assert_eq!(doc["b"]["c"], Hocon::Integer(2));
+    assert_eq!(doc["d"], Hocon::BadValue(Error::MissingKey));
+}
+
+#[cfg(feature = "encoding")]
+#[test]
+fn parse_include_with_encoding() {
+    let doc: Hocon = HoconLoader::new()
+        .load_file("tests/data/include_encoding.hocon")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(
+        doc["legacy"]["name"].as_string(),
+        Some(String::from("François"))
+    );
+}
+
+#[test]
+fn parse_include_from_classpath() {
+    let doc: Hocon = HoconLoader::new()
+        .with_classpath_resolver(|name| match name {
+            "basic.conf" => Some(std::path::PathBuf::from("tests/data/basic.conf")),
+            _ => None,
+        })
+        .load_file("tests/data/include_classpath.conf")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["a"], Hocon::Integer(5));
+    assert_eq!(doc["f"]["g"], Hocon::Boolean(false));
+
+    let doc = HoconLoader::new()
+        .strict()
+        .load_file("tests/data/include_classpath.conf");
+    assert_eq!(
+        doc.map(|_| ()),
+        Err(Error::Include {
+            path: String::from("basic.conf"),
+            chain: vec![String::from("tests/data/include_classpath.conf")],
+        })
+    );
+}
+
+#[test]
+fn parse_include_missing_with_strict_include() {
+    let doc: Hocon = HoconLoader::new()
+        .load_file("tests/data/include_missing.conf")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["b"], Hocon::Integer(1));
+
+    let doc = HoconLoader::new()
+        .strict_include()
+        .load_file("tests/data/include_missing.conf");
+    assert_eq!(
+        doc.map(|_| ()),
+        Err(Error::Include {
+            path: String::from("does_not_exist.conf"),
+            chain: vec![String::from("tests/data/include_missing.conf")],
+        })
+    );
+
+    let doc: Hocon = HoconLoader::new()
+        .strict_include()
+        .load_str("a = ${missing}")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(
+        doc["a"],
+        Hocon::BadValue(Error::KeyNotFound {
+            key: String::from("missing")
+        })
+    );
+}
+
+#[test]
+fn parse_include_with_file_include_disabled() {
+    let doc: Hocon = HoconLoader::new()
+        .no_file_include()
+        .load_file("tests/data/include.conf")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["a"], Hocon::Integer(5));
+    assert_eq!(doc["b"], Hocon::BadValue(Error::MissingKey));
+    assert_eq!(
+        doc["basic.conf"],
+        Hocon::BadValue(Error::Include {
+            path: String::from("basic.conf"),
+            chain: vec![String::from("tests/data/include.conf")],
+        })
+    );
+
+    let doc = HoconLoader::new()
+        .no_includes()
+        .with_classpath_resolver(|name| Some(std::path::Path::new("tests/data").join(name)))
+        .load_file("tests/data/include_classpath.conf")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["a"], Hocon::BadValue(Error::MissingKey));
+
+    let doc = HoconLoader::new()
+        .strict()
+        .no_file_include()
+        .load_file("tests/data/include_file.conf");
+    assert_eq!(
+        doc.map(|_| ()),
+        Err(Error::Include {
+            path: String::from("basic.conf"),
+            chain: vec![String::from("tests/data/include_file.conf")],
+        })
+    );
+}
+
+#[test]
+fn parse_include_missing_reports_include_chain() {
+    let doc: Hocon = HoconLoader::new()
+        .load_file("tests/data/include_chain.conf")
+        .expect("during test")
+        .hocon()
+        .expect("during test");
+    assert_eq!(doc["c"], Hocon::Integer(3));
+
+    let doc = HoconLoader::new()
+        .strict()
+        .load_file("tests/data/include_chain.conf");
+    let err = doc.map(|_| ()).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Include {
+            path: String::from("does_not_exist.conf"),
+            chain: vec![
+                String::from("include_chain_b.hocon"),
+                String::from("include_chain_a.hocon"),
+                String::from("tests/data/include_chain.conf"),
+            ],
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Error including document at '\"does_not_exist.conf\"' (from include_chain_b.hocon from include_chain_a.hocon from tests/data/include_chain.conf)"
+    );
+}